@@ -0,0 +1,175 @@
+//! Building entities in code.
+
+use std::collections::BTreeMap;
+
+use crate::entity::{ClaimValue, ClaimValueData, Entity, EntityType, SiteName, SitelinkValue};
+use crate::ids::{Pid, WikiId};
+use crate::text::Lang;
+
+/// A builder for making an [`Entity`] in code, such as for tests or for creating entities to add
+/// to a Wikibase, without filling in every field by hand.
+///
+/// Claims added with [`EntityBuilder::add_claim`] have normal rank and no qualifiers or
+/// references. Any claims without an ID are given a placeholder ID when the entity is built, like
+/// `Q42$1`; these aren't real statement IDs, they're only there to keep the claims distinct.
+///
+/// ## Example
+/// ```
+/// use wikidata::{consts, ClaimValueData, EntityBuilder, Lang, Qid, WikiId};
+/// let entity = EntityBuilder::new(WikiId::EntityId(Qid(42)))
+///     .label(Lang::en(), "Douglas Adams")
+///     .alias(Lang::en(), "Douglas Noël Adams")
+///     .add_claim(consts::INSTANCE_OF, ClaimValueData::Item(consts::HUMAN))
+///     .build();
+/// assert_eq!(entity.label("en"), Some("Douglas Adams"));
+/// assert_eq!(entity.instances(), vec![consts::HUMAN]);
+/// assert_eq!(entity.claims[0].1.id, "Q42$1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntityBuilder {
+    entity: Entity,
+}
+
+impl EntityBuilder {
+    /// Start building an entity with an ID. The entity type is inferred from the ID.
+    #[must_use]
+    pub fn new(id: WikiId) -> Self {
+        Self {
+            entity: Entity {
+                id,
+                claims: Vec::new(),
+                entity_type: EntityType::of(id),
+                descriptions: BTreeMap::new(),
+                labels: BTreeMap::new(),
+                aliases: BTreeMap::new(),
+                sitelinks: BTreeMap::new(),
+                lexeme: None,
+            },
+        }
+    }
+
+    /// Change the ID of the entity, along with the entity type.
+    #[must_use]
+    pub fn id(mut self, id: WikiId) -> Self {
+        self.entity.id = id;
+        self.entity.entity_type = EntityType::of(id);
+        self
+    }
+
+    /// Set the label in a language, replacing any existing label in that language.
+    #[must_use]
+    pub fn label(mut self, lang: Lang, label: impl Into<String>) -> Self {
+        self.entity.labels.insert(lang, label.into());
+        self
+    }
+
+    /// Set the description in a language, replacing any existing description in that language.
+    #[must_use]
+    pub fn description(mut self, lang: Lang, description: impl Into<String>) -> Self {
+        self.entity.descriptions.insert(lang, description.into());
+        self
+    }
+
+    /// Add an alias in a language, after any existing aliases in that language.
+    #[must_use]
+    pub fn alias(mut self, lang: Lang, alias: impl Into<String>) -> Self {
+        self.entity
+            .aliases
+            .entry(lang)
+            .or_default()
+            .push(alias.into());
+        self
+    }
+
+    /// Set the sitelink for a site, like `enwiki`, with no badges or URL.
+    #[must_use]
+    pub fn sitelink(mut self, site: impl Into<String>, title: impl Into<String>) -> Self {
+        self.entity.sitelinks.insert(
+            SiteName(site.into()),
+            SitelinkValue {
+                title: title.into(),
+                ..SitelinkValue::default()
+            },
+        );
+        self
+    }
+
+    /// Add a claim with normal rank and no qualifiers or references.
+    #[must_use]
+    pub fn add_claim(self, pid: Pid, data: ClaimValueData) -> Self {
+        self.add_claim_value(
+            pid,
+            ClaimValue {
+                data,
+                ..ClaimValue::default()
+            },
+        )
+    }
+
+    /// Add a claim, for when the rank, qualifiers, or references are needed.
+    #[must_use]
+    pub fn add_claim_value(mut self, pid: Pid, value: ClaimValue) -> Self {
+        self.entity.claims.push((pid, value));
+        self
+    }
+
+    /// Build the entity, giving placeholder IDs to claims without one.
+    #[must_use]
+    pub fn build(mut self) -> Entity {
+        let id = self.entity.id;
+        for (i, (_, claim)) in self.entity.claims.iter_mut().enumerate() {
+            if claim.id.is_empty() {
+                claim.id = format!("{}${}", id, i + 1);
+            }
+        }
+        self.entity
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::Rank;
+    use crate::ids::{consts, Lid, Qid};
+
+    #[test]
+    fn wikibase_json_roundtrip() {
+        // claims are grouped by property in Wikibase JSON, so they're added in that order here
+        let entity = EntityBuilder::new(WikiId::EntityId(Qid(42)))
+            .label(Lang::en(), "Douglas Adams")
+            .description(Lang::en(), "English writer and humorist")
+            .alias(Lang::en(), "Douglas Noël Adams")
+            .alias(Lang::en(), "Douglas Noel Adams")
+            .sitelink("enwiki", "Douglas Adams")
+            .add_claim(consts::INSTANCE_OF, ClaimValueData::Item(consts::HUMAN))
+            .add_claim_value(
+                consts::INSTANCE_OF,
+                ClaimValue {
+                    data: ClaimValueData::Item(Qid(1)),
+                    rank: Rank::Deprecated,
+                    id: "Q42$deprecated".to_string(),
+                    ..ClaimValue::default()
+                },
+            )
+            .add_claim(consts::DATE_OF_DEATH, ClaimValueData::UnknownValue)
+            .build();
+        let ids: Vec<&str> = entity
+            .claims
+            .iter()
+            .map(|(_, claim)| &claim.id[..])
+            .collect();
+        assert_eq!(ids, ["Q42$1", "Q42$deprecated", "Q42$3"]);
+        assert_eq!(
+            Entity::from_json(entity.to_wikibase_json()).unwrap(),
+            entity
+        );
+    }
+
+    #[test]
+    fn entity_type() {
+        let builder = EntityBuilder::new(WikiId::EntityId(Qid(1)));
+        assert_eq!(builder.clone().build().entity_type, EntityType::Entity);
+        let lexeme = builder.id(WikiId::LexemeId(Lid(1))).build();
+        assert_eq!(lexeme.entity_type, EntityType::Lexeme);
+    }
+}
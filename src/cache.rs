@@ -0,0 +1,173 @@
+//! A cache for entities, keyed by their ID.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::entity::Entity;
+use crate::ids::WikiId;
+
+/// A thread-safe least-recently-used cache of entities, keyed by [`WikiId`].
+///
+/// The cache doesn't do any network requests itself: [`EntityCache::get_or_fetch`] takes a
+/// function that gets the entity (e.g. by requesting its [`json_url`](crate::Qid::json_url) and
+/// parsing the result with [`Entity::from_json`]), which is only called on a cache miss.
+///
+/// ## Example
+/// ```
+/// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+/// use wikidata::{Entity, EntityCache, Qid, WikiId};
+/// let cache = EntityCache::new(100);
+/// let fetch = |_id| Entity::from_json(j.clone());
+/// let q42 = cache.get_or_fetch(WikiId::EntityId(Qid(42)), fetch).unwrap();
+/// // the second call is served from the cache
+/// let again = cache
+///     .get_or_fetch(WikiId::EntityId(Qid(42)), |_| -> Result<Entity, ()> { unreachable!() })
+///     .unwrap();
+/// assert_eq!(q42, again);
+/// ```
+#[derive(Debug)]
+pub struct EntityCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    entries: HashMap<WikiId, (Arc<Entity>, u64)>,
+    clock: u64,
+}
+
+impl CacheInner {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl EntityCache {
+    /// Create an empty cache that holds at most `capacity` entities. Once it is full, the least
+    /// recently used entity is evicted to make room for new ones. A capacity of zero disables
+    /// caching.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// The maximum number of entities the cache can hold.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of entities currently in the cache.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an entity from the cache, if it's there.
+    #[must_use]
+    pub fn get(&self, id: WikiId) -> Option<Arc<Entity>> {
+        let mut inner = self.lock();
+        let tick = inner.tick();
+        let (entity, last_used) = inner.entries.get_mut(&id)?;
+        *last_used = tick;
+        Some(Arc::clone(entity))
+    }
+
+    /// Add an entity to the cache, evicting the least recently used entity if the cache is full.
+    pub fn insert(&self, id: WikiId, entity: Arc<Entity>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.lock();
+        if !inner.entries.contains_key(&id) && inner.entries.len() >= self.capacity {
+            let lru = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(id, _)| *id);
+            if let Some(lru) = lru {
+                inner.entries.remove(&lru);
+            }
+        }
+        let tick = inner.tick();
+        inner.entries.insert(id, (entity, tick));
+    }
+
+    /// Remove every entity from the cache.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Get an entity from the cache, or if it isn't there, get it with `fetch` and add it to the
+    /// cache.
+    ///
+    /// The cache isn't locked while `fetch` runs, so if several threads request the same missing
+    /// entity at once, each of them may call `fetch`.
+    ///
+    /// # Errors
+    /// If the entity isn't in the cache and `fetch` returns an error, that error is returned and
+    /// nothing is cached.
+    pub fn get_or_fetch<E>(
+        &self,
+        id: WikiId,
+        fetch: impl FnOnce(WikiId) -> Result<Entity, E>,
+    ) -> Result<Arc<Entity>, E> {
+        if let Some(entity) = self.get(id) {
+            return Ok(entity);
+        }
+        let entity = Arc::new(fetch(id)?);
+        self.insert(id, Arc::clone(&entity));
+        Ok(entity)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheInner> {
+        // the cache is never left in an inconsistent state, so a poisoned lock is still usable
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::EntityType;
+    use crate::ids::Qid;
+    use std::collections::BTreeMap;
+
+    fn entity(id: u64) -> Entity {
+        Entity {
+            id: WikiId::EntityId(Qid(id)),
+            claims: Vec::new(),
+            entity_type: EntityType::Entity,
+            descriptions: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            sitelinks: BTreeMap::new(),
+            lexeme: None,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = EntityCache::new(2);
+        let id = |n| WikiId::EntityId(Qid(n));
+        cache.insert(id(1), Arc::new(entity(1)));
+        cache.insert(id(2), Arc::new(entity(2)));
+        assert!(cache.get(id(1)).is_some());
+        cache.insert(id(3), Arc::new(entity(3)));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(id(1)).is_some());
+        assert!(cache.get(id(2)).is_none());
+        assert!(cache.get(id(3)).is_some());
+    }
+}
@@ -1,13 +1,24 @@
-use std::{collections::BTreeMap, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    hash::{Hash, Hasher},
+    mem,
+    str::FromStr,
+};
 
-use crate::ids::{consts, Fid, Lid, Pid, Qid, Sid, WikiId};
+use crate::ids::{consts, EntityOrIri, Fid, Lid, Mid, Pid, Qid, Sid, WikiId};
+use crate::lexeme::LexemeData;
 use crate::text::{Lang, Text};
-use chrono::{DateTime, TimeZone, Utc};
+use crate::time::WbTime;
+use chrono::{DateTime, Duration, FixedOffset, Offset, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// A Wikibase entity: this could be an entity, property, or lexeme.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Entities can be hashed, with the same caveat about `NaN`s as [`ClaimValueData`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Entity {
     /// Unique identifier
     pub id: WikiId,
@@ -16,18 +27,32 @@ pub struct Entity {
     /// The type of the entity.
     pub entity_type: EntityType,
     /// All of the descriptions in all known languages.
+    ///
+    /// When parsing, empty or whitespace-only descriptions are left out, so a language is only
+    /// present if it has a description that can be shown.
     pub descriptions: BTreeMap<Lang, String>,
     /// All of the labels in all known languages.
+    ///
+    /// When parsing, empty or whitespace-only labels are left out, so a language is only present
+    /// if it has a label that can be shown.
     pub labels: BTreeMap<Lang, String>,
     /// Known aliases of the item.
+    ///
+    /// When parsing, empty or whitespace-only aliases are left out, along with languages that
+    /// have no other aliases.
     pub aliases: BTreeMap<Lang, Vec<String>>,
     /// site links (e.g. to wikipedia, wikivoyage, ...)
     pub sitelinks: BTreeMap<SiteName, SitelinkValue>,
+    /// The lemmas, language, lexical category, forms, and senses of a lexeme. This is `None` for
+    /// other types of entities, and for lexemes from partial fetches that leave them out.
+    #[serde(default)]
+    pub lexeme: Option<LexemeData>,
 }
 
-/// The type of entity: normal entity with a Qid, a property with a Pid, or a lexeme with a Lid.
+/// The type of entity: normal entity with a Qid, a property with a Pid, a lexeme with a Lid, or a
+/// Commons media file with a Mid.
 ///
-/// EntitySchemas (with E IDs) are currently unsupported.
+/// `EntitySchemas` (with E IDs) are currently unsupported.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum EntityType {
@@ -37,9 +62,17 @@ pub enum EntityType {
     Property,
     /// An entity with a Lid.
     Lexeme,
+    /// An entity with a Mid.
+    MediaInfo,
 }
 
 /// Data relating to a claim value.
+///
+/// Claim data implements `Eq` and `Hash` even though some variants contain floats, so that it
+/// can be used in hash maps and sets. Floats are hashed by their bits, with `-0.0` treated as
+/// `0.0` so that equal values hash the same. The caveat is `NaN`: data containing a `NaN` isn't
+/// equal to itself, so it can be inserted into a set but never found again. Wikibase never
+/// produces `NaN`s, so this only matters for data that is made by hand.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum ClaimValueData {
     /// The ID of a file on Wikimedia Commons.
@@ -50,10 +83,11 @@ pub enum ClaimValueData {
         lat: f64,
         /// Longitude.
         lon: f64,
-        /// How many degrees of distance of precision there are.
-        precision: f64,
+        /// How many degrees of distance of precision there are, if known. This is `None` when
+        /// the precision is missing, which happens in some older data.
+        precision: Option<f64>,
         /// The globe the coordnaties are on, usually [Earth](consts::EARTH).
-        globe: Qid,
+        globe: EntityOrIri,
     },
     /// A Wikidata item.
     Item(Qid),
@@ -64,6 +98,12 @@ pub enum ClaimValueData {
     /// Text with a language.
     MonolingualText(Text),
     /// The same text, translated across multiple languages.
+    ///
+    /// Wikibase doesn't have a multilingual datatype, so [`ClaimValueData::parse_snak`] never
+    /// produces this: monolingual text snaks are always parsed to
+    /// [`MonolingualText`](ClaimValueData::MonolingualText). Use
+    /// [`Entity::collect_multilingual`] to combine all of the monolingual text values of a
+    /// property into one of these.
     MultilingualText(Vec<Text>),
     /// An external identifier.
     ExternalID(String),
@@ -75,12 +115,13 @@ pub enum ClaimValueData {
         lower_bound: Option<f64>,
         /// The highest possible value. If this isn't present then it is exactly the amount.
         upper_bound: Option<f64>,
-        /// The units used.
-        unit: Option<Qid>, // *could* be any IRI but in practice almost all are Wikidata entity IRIs
+        /// The units used, usually a Wikidata entity.
+        unit: Option<EntityOrIri>,
     },
     /// A point in time time.
     DateTime {
-        /// The time as a Chrono DateTime.
+        /// The time as a Chrono `DateTime`. If the snak had a time zone offset and a precision
+        /// finer than a day, the time is converted from that offset to UTC.
         date_time: DateTime<chrono::offset::Utc>,
         /// The precision of the date:
         ///
@@ -101,15 +142,32 @@ pub enum ClaimValueData {
         /// | `12` | hour (deprecated) |
         /// | `13` | minute (deprecated) |
         /// | `14` | second (deprecated) |
+        ///
+        /// The deprecated precisions still show up in older data, and are kept as-is.
         precision: u8,
+        /// The calendar model the date is written in, usually
+        /// [`PROLEPTIC_GREGORIAN_CALENDAR`](consts::PROLEPTIC_GREGORIAN_CALENDAR) or
+        /// [`PROLEPTIC_JULIAN_CALENDAR`](consts::PROLEPTIC_JULIAN_CALENDAR). The `date_time` is
+        /// the date exactly as written in this calendar, it isn't converted to Gregorian.
+        calendar: Qid,
+    },
+    /// A point in time that can't be represented by a Chrono `DateTime`, usually because the
+    /// year is too far in the past or future (like the formation of the Earth). Times that *can*
+    /// be represented are always a [`DateTime`](ClaimValueData::DateTime) instead.
+    OutOfRangeTime {
+        /// The time, as Wikibase stores it.
+        time: WbTime,
+        /// The calendar model the time is written in, as in
+        /// [`DateTime`](ClaimValueData::DateTime).
+        calendar: Qid,
     },
     /// A URL.
     Url(String),
-    /// A LaTeX math expression.
+    /// A `LaTeX` math expression.
     MathExpr(String),
     /// A geometric shape. The value of the string is currently unspecified.
     GeoShape(String),
-    /// LilyPond musical notation.
+    /// `LilyPond` musical notation.
     MusicNotation(String),
     /// ID of a file with tabular data on Wikimedia commons.
     TabularData(String),
@@ -126,8 +184,103 @@ pub enum ClaimValueData {
     UnknownValue,
 }
 
+/// The datatype of a property, as used in the `datatype` field of snaks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Datatype {
+    /// `commonsMedia`
+    CommonsMedia,
+    /// `globe-coordinate`
+    GlobeCoordinate,
+    /// `wikibase-item`
+    Item,
+    /// `wikibase-property`
+    Property,
+    /// `string`
+    String,
+    /// `monolingualtext`
+    MonolingualText,
+    /// `external-id`
+    ExternalID,
+    /// `quantity`
+    Quantity,
+    /// `time`
+    Time,
+    /// `url`
+    Url,
+    /// `math`
+    MathExpr,
+    /// `geo-shape`
+    GeoShape,
+    /// `musical-notation`
+    MusicNotation,
+    /// `tabular-data`
+    TabularData,
+    /// `wikibase-lexeme`
+    Lexeme,
+    /// `wikibase-form`
+    Form,
+    /// `wikibase-sense`
+    Sense,
+}
+
+impl Datatype {
+    /// The name of the datatype, as used in the Wikibase JSON representation.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Datatype::CommonsMedia => "commonsMedia",
+            Datatype::GlobeCoordinate => "globe-coordinate",
+            Datatype::Item => "wikibase-item",
+            Datatype::Property => "wikibase-property",
+            Datatype::String => "string",
+            Datatype::MonolingualText => "monolingualtext",
+            Datatype::ExternalID => "external-id",
+            Datatype::Quantity => "quantity",
+            Datatype::Time => "time",
+            Datatype::Url => "url",
+            Datatype::MathExpr => "math",
+            Datatype::GeoShape => "geo-shape",
+            Datatype::MusicNotation => "musical-notation",
+            Datatype::TabularData => "tabular-data",
+            Datatype::Lexeme => "wikibase-lexeme",
+            Datatype::Form => "wikibase-form",
+            Datatype::Sense => "wikibase-sense",
+        }
+    }
+}
+
+impl FromStr for Datatype {
+    type Err = EntityError;
+
+    fn from_str(x: &str) -> Result<Self, Self::Err> {
+        Ok(match x {
+            "commonsMedia" => Datatype::CommonsMedia,
+            "globe-coordinate" => Datatype::GlobeCoordinate,
+            "wikibase-item" => Datatype::Item,
+            "wikibase-property" => Datatype::Property,
+            "string" => Datatype::String,
+            "monolingualtext" => Datatype::MonolingualText,
+            "external-id" => Datatype::ExternalID,
+            "quantity" => Datatype::Quantity,
+            "time" => Datatype::Time,
+            "url" => Datatype::Url,
+            "math" => Datatype::MathExpr,
+            "geo-shape" => Datatype::GeoShape,
+            "musical-notation" => Datatype::MusicNotation,
+            "tabular-data" => Datatype::TabularData,
+            "wikibase-lexeme" => Datatype::Lexeme,
+            "wikibase-form" => Datatype::Form,
+            "wikibase-sense" => Datatype::Sense,
+            _ => return Err(EntityError::UnknownDatatype),
+        })
+    }
+}
+
 /// A statement rank.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub enum Rank {
     /// The deprecated rank, indicating outdated/wrong info. Deprecated claims should usually be
     /// ignored.
@@ -139,6 +292,18 @@ pub enum Rank {
     Preferred,
 }
 
+impl EntityType {
+    /// The type of entity that has an ID. Forms and senses are part of a lexeme.
+    pub(crate) fn of(id: WikiId) -> Self {
+        match id {
+            WikiId::EntityId(_) => EntityType::Entity,
+            WikiId::PropertyId(_) => EntityType::Property,
+            WikiId::LexemeId(_) | WikiId::FormId(_) | WikiId::SenseId(_) => EntityType::Lexeme,
+            WikiId::MediaInfoId(_) => EntityType::MediaInfo,
+        }
+    }
+}
+
 impl FromStr for Rank {
     type Err = EntityError;
 
@@ -152,8 +317,36 @@ impl FromStr for Rank {
     }
 }
 
+/// The type of a snak: whether it has a value, or says that the value is unknown or that there
+/// is no value.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
+pub enum Snaktype {
+    /// The snak has a value.
+    #[default]
+    Value,
+    /// The snak has a value, but it isn't known what it is. The data of these snaks is
+    /// [`UnknownValue`](ClaimValueData::UnknownValue).
+    SomeValue,
+    /// The snak says there is no value. The data of these snaks is
+    /// [`NoValue`](ClaimValueData::NoValue).
+    NoValue,
+}
+
+impl FromStr for Snaktype {
+    type Err = EntityError;
+
+    fn from_str(x: &str) -> Result<Self, Self::Err> {
+        match x {
+            "value" => Ok(Self::Value),
+            "somevalue" => Ok(Self::SomeValue),
+            "novalue" => Ok(Self::NoValue),
+            _ => Err(EntityError::InvalidSnaktype),
+        }
+    }
+}
+
 /// A group of claims that make up a single reference.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ReferenceGroup {
     /// All of the claims.
     pub claims: Vec<(Pid, ClaimValueData)>,
@@ -162,7 +355,7 @@ pub struct ReferenceGroup {
 }
 
 /// A claim value.
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct ClaimValue {
     /// The data of the claim.
     pub data: ClaimValueData,
@@ -176,12 +369,44 @@ pub struct ClaimValue {
     pub references: Vec<ReferenceGroup>,
 }
 
+/// A flat, spreadsheet-like record of some of an entity's data, as returned by
+/// [`Entity::to_flat_record`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FlatRecord {
+    /// The entity's ID, like `Q42`.
+    pub id: String,
+    /// The entity's label in the requested language, if it has one.
+    pub label: Option<String>,
+    /// The best value of each requested property, in the order they were requested. The value
+    /// is `None` if the entity has no value for the property, or if the best value is "no value"
+    /// or "unknown value".
+    pub values: Vec<(Pid, Option<String>)>,
+}
+
+/// Options for how [`Entity::from_json_with_options`] parses entities. The default options are
+/// the ones used by [`Entity::from_json`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParseOptions {
+    /// Check that the `property` of each claim's mainsnak is the same as the property the claim
+    /// is listed under, and return [`EntityError::PropertyMismatch`] if it isn't. By default the
+    /// property the claim is listed under is used and the mainsnak's property is ignored.
+    pub check_property_mismatch: bool,
+    /// The maximum number of claims to parse. Once this many claims have been parsed, any
+    /// remaining claims are left out of the entity, unless
+    /// [`error_on_claim_limit`](ParseOptions::error_on_claim_limit) is set. By default there is
+    /// no limit.
+    pub max_claims: Option<usize>,
+    /// Return [`EntityError::ClaimLimitExceeded`] instead of truncating the claims when the
+    /// entity has more than [`max_claims`](ParseOptions::max_claims) claims.
+    pub error_on_claim_limit: bool,
+}
+
 /// A site name, as used in the sitelinks.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct SiteName(pub String);
 
 /// A sitelink value.
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct SitelinkValue {
     /// The title of the site link.
     pub title: String,
@@ -192,7 +417,8 @@ pub struct SitelinkValue {
 }
 
 impl Entity {
-    /// All of the values of "instance of" on the entity.
+    /// All of the values of "instance of" on the entity. This includes the values of deprecated
+    /// statements; use [`Entity::instance_claims`] to check the rank of each statement.
     #[must_use]
     pub fn instances(&self) -> Vec<Qid> {
         let mut instances = Vec::with_capacity(1);
@@ -200,13 +426,64 @@ impl Entity {
             if *pid == consts::INSTANCE_OF {
                 if let ClaimValueData::Item(qid) = claim.data {
                     instances.push(qid);
-                };
-            };
+                }
+            }
         }
         instances.shrink_to_fit();
         instances
     }
 
+    /// All of the "instance of" statements on the entity, including their rank, qualifiers and
+    /// references. Like [`Entity::instances`], this includes deprecated statements.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, ClaimValueData, Rank};
+    /// let instances = q42.instance_claims();
+    /// assert_eq!(instances[0].data, ClaimValueData::Item(consts::HUMAN));
+    /// assert_eq!(instances[0].rank, Rank::Normal);
+    /// ```
+    #[must_use]
+    pub fn instance_claims(&self) -> Vec<&ClaimValue> {
+        self.pid_claims(consts::INSTANCE_OF).collect()
+    }
+
+    /// The edges from this entity to its classes in the class graph, as
+    /// `(self_qid, property, parent_qid)` triples, where `property` is either
+    /// [instance of](consts::INSTANCE_OF) or [subclass of](consts::SUBCLASS_OF). Deprecated
+    /// claims are skipped.
+    ///
+    /// This only looks at the entity itself; to find all of the classes an entity belongs to
+    /// transitively, collect the edges of many entities (e.g. from a dump) and compute the
+    /// closure. Entities without a Qid have no edges.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, Qid};
+    /// assert_eq!(q42.class_edges(), vec![(Qid(42), consts::INSTANCE_OF, consts::HUMAN)]);
+    /// ```
+    #[must_use]
+    pub fn class_edges(&self) -> Vec<(Qid, Pid, Qid)> {
+        let WikiId::EntityId(qid) = self.id else {
+            return Vec::new();
+        };
+        self.claims
+            .iter()
+            .filter(|(pid, claim)| {
+                (*pid == consts::INSTANCE_OF || *pid == consts::SUBCLASS_OF)
+                    && claim.rank != Rank::Deprecated
+            })
+            .filter_map(|(pid, claim)| match claim.data {
+                ClaimValueData::Item(parent) => Some((qid, *pid, parent)),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// When the entity started existing.
     #[must_use]
     pub fn start_time(&self) -> Option<DateTime<chrono::offset::Utc>> {
@@ -214,8 +491,8 @@ impl Entity {
             if *pid == consts::DATE_OF_BIRTH {
                 if let ClaimValueData::DateTime { date_time, .. } = claim.data {
                     return Some(date_time);
-                };
-            };
+                }
+            }
         }
         None
     }
@@ -227,12 +504,108 @@ impl Entity {
             if *pid == consts::DATE_OF_DEATH {
                 if let ClaimValueData::DateTime { date_time, .. } = claim.data {
                     return Some(date_time);
-                };
-            };
+                }
+            }
         }
         None
     }
 
+    /// Whether the entity carries no data besides its ID and type: it has no claims, labels,
+    /// descriptions, aliases, or sitelinks. This can happen with partial fetches, like a fetch of
+    /// only the sitelinks of an entity that has none.
+    ///
+    /// ## Example
+    /// ```
+    /// let j = serde_json::json!({"type": "item", "id": "Q1"});
+    /// assert!(wikidata::Entity::from_json(j).unwrap().is_empty());
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert!(!q42.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+            && self.labels.is_empty()
+            && self.descriptions.is_empty()
+            && self.aliases.is_empty()
+            && self.sitelinks.is_empty()
+    }
+
+    /// Every time in the entity's claims, along with its property and
+    /// [precision](ClaimValueData::DateTime), in the order of [`Entity::claims`]. This is useful
+    /// for building a timeline of an entity.
+    ///
+    /// For each property, only the claims with the best rank are used, so if any claim for a
+    /// property is preferred, only the preferred claims are used. Deprecated claims are never
+    /// used. Times in qualifiers (like the start time of a spouse) and references aren't
+    /// included.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::consts;
+    /// let dates = q42.all_dates();
+    /// let (_, birth, precision) = dates.iter().find(|(pid, ..)| *pid == consts::DATE_OF_BIRTH).unwrap();
+    /// assert_eq!(birth.format("%Y-%m-%d").to_string(), "1952-03-11");
+    /// assert_eq!(*precision, 11);
+    /// assert!(dates.iter().any(|(pid, ..)| *pid == consts::DATE_OF_DEATH));
+    /// ```
+    #[must_use]
+    pub fn all_dates(&self) -> Vec<(Pid, DateTime<Utc>, u8)> {
+        best_rank_claims(self.claims.iter())
+            .filter_map(|(pid, claim)| match claim.data {
+                ClaimValueData::DateTime {
+                    date_time,
+                    precision,
+                    ..
+                } => Some((*pid, date_time, precision)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Build an entity for a test, with the given claims and nothing else. The claims all have
+    /// normal rank, an empty claim ID, and no qualifiers or references. The entity type is
+    /// inferred from the ID.
+    ///
+    /// This is only available with the `test-util` feature.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{consts, ClaimValueData, Entity, Qid, WikiId};
+    /// let entity = Entity::mock(
+    ///     WikiId::EntityId(Qid(42)),
+    ///     vec![(consts::INSTANCE_OF, ClaimValueData::Item(consts::HUMAN))],
+    /// );
+    /// assert_eq!(entity.instances(), vec![consts::HUMAN]);
+    /// ```
+    #[cfg(feature = "test-util")]
+    #[must_use]
+    pub fn mock(id: WikiId, claims: Vec<(Pid, ClaimValueData)>) -> Self {
+        Self {
+            id,
+            claims: claims
+                .into_iter()
+                .map(|(pid, data)| {
+                    (
+                        pid,
+                        ClaimValue {
+                            data,
+                            ..ClaimValue::default()
+                        },
+                    )
+                })
+                .collect(),
+            entity_type: EntityType::of(id),
+            descriptions: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            sitelinks: BTreeMap::new(),
+            lexeme: None,
+        }
+    }
+
     /// Construct an entity from the Wikibase JSON repersentation. The input can either be an
     /// object directly containing the Wikibase entity representation, or a multi-entity object
     /// returned by some endpoints such as `Special:EntityData`. Multi-entity objects must only
@@ -240,7 +613,60 @@ impl Entity {
     ///
     /// # Errors
     /// If the JSON reperesntation can't be parsed to an `Entity`, an `EntityError` will be returned.
-    pub fn from_json(mut json: Value) -> Result<Self, EntityError> {
+    pub fn from_json(json: Value) -> Result<Self, EntityError> {
+        Self::from_json_with_options(json, &ParseOptions::default())
+    }
+
+    /// Construct every entity in a multi-entity object, like the ones returned by the
+    /// `wbgetentities` API (see [`wbgetentities_url`](crate::wbgetentities_url)). Each entity is
+    /// returned along with its key in the object, which is the ID that was asked for: for
+    /// redirected entities, this is the ID of the redirect, while [`Entity::id`] is the ID of the
+    /// entity it redirects to. Missing entities (which have a `missing` key) are skipped.
+    ///
+    /// ## Example
+    /// ```
+    /// # let q42: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// use wikidata::{Entity, Qid, WikiId};
+    /// let json = serde_json::json!({
+    ///     "entities": {
+    ///         "Q42": q42["entities"]["Q42"],
+    ///         "Q999999999": { "id": "Q999999999", "missing": "" }
+    ///     }
+    /// });
+    /// let entities = Entity::from_json_multi(json).unwrap();
+    /// assert_eq!(entities.len(), 1);
+    /// assert_eq!(entities[0].0, WikiId::EntityId(Qid(42)));
+    /// ```
+    ///
+    /// # Errors
+    /// If there's no `entities` object, [`EntityError::NoEntities`] is returned. If any of the
+    /// entities can't be parsed, the first error is returned.
+    pub fn from_json_multi(mut json: Value) -> Result<Vec<(WikiId, Self)>, EntityError> {
+        let entities = json
+            .get_mut("entities")
+            .ok_or(EntityError::NoEntities)?
+            .as_object_mut()
+            .ok_or(EntityError::ExpectedObject)?;
+        let mut parsed = Vec::with_capacity(entities.len());
+        for (key, entity) in entities.iter_mut() {
+            if entity.get("missing").is_some() {
+                continue;
+            }
+            let id = WikiId::from_str(key).map_err(|_| EntityError::BadId)?;
+            parsed.push((id, Self::from_json(entity.take())?));
+        }
+        Ok(parsed)
+    }
+
+    /// Construct an entity from the Wikibase JSON repersentation, like [`Entity::from_json`], but
+    /// with non-default [`ParseOptions`].
+    ///
+    /// # Errors
+    /// If the JSON reperesntation can't be parsed to an `Entity`, an `EntityError` will be returned.
+    pub fn from_json_with_options(
+        mut json: Value,
+        options: &ParseOptions,
+    ) -> Result<Self, EntityError> {
         let mut json = match json.get_mut("entities") {
             Some(ents) => {
                 let obj = ents.as_object_mut().ok_or(EntityError::ExpectedObject)?;
@@ -258,68 +684,69 @@ impl Entity {
             None => json,
         };
 
-        let raw_id: &str = json
-            .get_mut("id")
-            .ok_or(EntityError::ExpectedObject)?
-            .as_str()
-            .ok_or(EntityError::ExpectedKeyvalTextString)?;
-
-        let id: WikiId = match WikiId::from_str(raw_id) {
-            Ok(id) => id,
-            _ => return Err(EntityError::NoId),
-        };
+        // redirects are stored as `{"entity": "Q123", "redirect": "Q456"}`
+        if let Some(target) = json.get("redirect").and_then(Value::as_str) {
+            let from = json
+                .get("entity")
+                .and_then(Value::as_str)
+                .ok_or(EntityError::NoId)?;
+            return Err(EntityError::Redirect {
+                from: WikiId::from_str(from).map_err(|_| EntityError::BadId)?,
+                to: WikiId::from_str(target).map_err(|_| EntityError::BadId)?,
+            });
+        }
 
-        macro_rules! text_keyval {
-            ($key:literal) => {{
-                match json.get($key) {
-                    Some(json_map) => {
-                        let json_map = json_map.as_object().ok_or(EntityError::ExpectedObject)?;
-                        let mut map = BTreeMap::new();
-                        for (key, val) in json_map {
-                            map.insert(
-                                Lang(key.to_string()),
-                                val.as_object()
-                                    .ok_or(EntityError::ExpectedObject)?
-                                    .get("value")
-                                    .ok_or(EntityError::ExpectedLangString)?
-                                    .as_str()
-                                    .ok_or(EntityError::ExpectedKeyvalTextString)?
-                                    .to_string(),
-                            );
-                        }
-                        map
-                    }
-                    None => BTreeMap::new(),
+        let id: WikiId = match json.get("id").ok_or(EntityError::ExpectedObject)? {
+            Value::String(raw_id) => match WikiId::from_str(raw_id) {
+                Ok(id) => id,
+                _ => return Err(EntityError::NoId),
+            },
+            // some malformed exports have the bare number as the ID, so get the prefix from the
+            // entity type
+            Value::Number(num) => {
+                let num = num.as_u64().ok_or(EntityError::NonStringId)?;
+                match json.get("type").and_then(Value::as_str) {
+                    Some("item") => WikiId::EntityId(Qid(num)),
+                    Some("property") => WikiId::PropertyId(Pid(num)),
+                    Some("lexeme") => WikiId::LexemeId(Lid(num)),
+                    Some("mediainfo") => WikiId::MediaInfoId(Mid(num)),
+                    _ => return Err(EntityError::NonStringId),
                 }
-            }};
-        }
+            }
+            _ => return Err(EntityError::NonStringId),
+        };
 
-        let labels = text_keyval!("labels");
-        let descriptions = text_keyval!("descriptions");
+        let labels = parse_terms(json.get("labels"))?;
+        let descriptions = parse_terms(json.get("descriptions"))?;
 
         let aliases = match json.get("aliases") {
             Some(json_map) => {
                 let json_map = json_map.as_object().ok_or(EntityError::ExpectedObject)?;
                 let mut map = BTreeMap::new();
                 for (key, val) in json_map {
-                    map.insert(
-                        Lang(key.to_string()),
-                        val.as_array()
-                            .ok_or(EntityError::ExpectedAliasArray)?
-                            .iter()
-                            .filter_map(|val| {
-                                Some(
-                                    val.get("value")
-                                        .ok_or(EntityError::ExpectedTextValue)
-                                        .ok()?
-                                        .as_str()
-                                        .ok_or(EntityError::ExpectedAliasString)
-                                        .ok()?
-                                        .to_string(),
-                                )
-                            })
-                            .collect(),
-                    );
+                    let aliases: Vec<String> = val
+                        .as_array()
+                        .ok_or(EntityError::ExpectedAliasArray)?
+                        .iter()
+                        .filter_map(|val| {
+                            if let Value::String(s) = val {
+                                return Some(s.clone());
+                            }
+                            Some(
+                                val.get("value")
+                                    .ok_or(EntityError::ExpectedTextValue)
+                                    .ok()?
+                                    .as_str()
+                                    .ok_or(EntityError::ExpectedAliasString)
+                                    .ok()?
+                                    .to_string(),
+                            )
+                        })
+                        .filter(|alias| !alias.trim().is_empty())
+                        .collect();
+                    if !aliases.is_empty() {
+                        map.insert(Lang(key.clone()), aliases);
+                    }
                 }
                 map
             }
@@ -332,20 +759,28 @@ impl Entity {
                 let mut map = BTreeMap::new();
                 for (key, val) in json_map {
                     let obj = val.as_object().ok_or(EntityError::ExpectedObject)?;
+                    let title = match obj.get("title") {
+                        None | Some(Value::Null) => return Err(EntityError::MissingSitelinkTitle),
+                        Some(title) => {
+                            title.as_str().ok_or(EntityError::ExpectedSiteTitleString)?
+                        }
+                    };
+                    if title.is_empty() {
+                        return Err(EntityError::MissingSitelinkTitle);
+                    }
+                    // partial sitelinks sometimes leave out the badges, treat that as no badges
+                    let badges = match obj.get("badges") {
+                        Some(badges) => badges
+                            .as_array()
+                            .ok_or(EntityError::ExpectedSiteBadgesArray)?
+                            .as_slice(),
+                        None => &[],
+                    };
                     map.insert(
-                        SiteName(key.to_string()),
+                        SiteName(key.clone()),
                         SitelinkValue {
-                            title: obj
-                                .get("title")
-                                .ok_or(EntityError::ExpectedSiteTitleString)?
-                                .as_str()
-                                .ok_or(EntityError::ExpectedKeyvalTextString)?
-                                .to_string(),
-                            badges: obj
-                                .get("badges")
-                                .ok_or(EntityError::ExpectedSiteBadgesArray)?
-                                .as_array()
-                                .ok_or(EntityError::ExpectedSiteBadgesArray)?
+                            title: title.to_string(),
+                            badges: badges
                                 .iter()
                                 .filter_map(|val| {
                                     let raw_id = val
@@ -355,7 +790,7 @@ impl Entity {
                                     Qid::from_str(raw_id).ok()
                                 })
                                 .collect(),
-                            url: obj.get("url").map(|val| val.to_string()),
+                            url: obj.get("url").and_then(Value::as_str).map(str::to_string),
                         },
                     );
                 }
@@ -368,15 +803,24 @@ impl Entity {
             Some("item") => EntityType::Entity,
             Some("property") => EntityType::Property,
             Some("lexeme") => EntityType::Lexeme,
+            Some("mediainfo") => EntityType::MediaInfo,
             _ => return Err(EntityError::NoEntityType),
         };
 
         let mut claims = Vec::new();
-        for (pid, claim_list) in json
-            .get_mut("claims")
-            .ok_or(EntityError::NoClaims)?
-            .as_object_mut()
-            .ok_or(EntityError::ExpectedObject)?
+        // MediaInfo entities call their claims statements
+        let claims_key = if json.get("claims").is_none() && json.get("statements").is_some() {
+            "statements"
+        } else {
+            "claims"
+        };
+        // partial fetches (e.g. `props=sitelinks`) have no claims at all
+        'claims: for (pid, claim_list) in json
+            .get_mut(claims_key)
+            .map(|claims| claims.as_object_mut().ok_or(EntityError::ExpectedObject))
+            .transpose()?
+            .into_iter()
+            .flatten()
         {
             let pid = Pid::from_str(pid).map_err(|_| EntityError::BadId)?;
             for claim in claim_list
@@ -384,102 +828,35 @@ impl Entity {
                 .ok_or(EntityError::ExpectedClaimArray)?
                 .iter_mut()
             {
-                let references =
-                    if let Some(ref_groups) = claim.get("references").and_then(Value::as_array) {
-                        let mut references = Vec::with_capacity(ref_groups.len());
-                        for group in ref_groups {
-                            let snaks = group
-                                .get("snaks")
-                                .ok_or(EntityError::NoReferenceSnaks)?
-                                .as_object()
-                                .ok_or(EntityError::ExpectedObject)?;
-                            let mut claims = Vec::with_capacity(snaks.len());
-                            for pid in group
-                                .get("snaks-order")
-                                .and_then(Value::as_array)
-                                .ok_or(EntityError::NoSnakOrder)?
-                            {
-                                let pid = pid.as_str().ok_or(EntityError::ExpectedPidString)?;
-                                for subsnak in snaks
-                                    .get(pid)
-                                    .ok_or(EntityError::SnaksOrderIncludesNonSnak)?
-                                    .as_array()
-                                    .ok_or(EntityError::ExpectedReferenceArray)?
-                                {
-                                    claims.push((
-                                        Pid::from_str(pid).map_err(|_| EntityError::BadId)?,
-                                        ClaimValueData::parse_snak(subsnak.clone())?,
-                                    ));
-                                }
-                            }
-                            claims.shrink_to_fit();
-                            references.push(ReferenceGroup {
-                                claims,
-                                hash: group
-                                    .get("hash")
-                                    .ok_or(EntityError::NoHash)?
-                                    .as_str()
-                                    .ok_or(EntityError::ExpectedHashString)?
-                                    .to_string(),
-                            });
-                        }
-                        references
-                    } else {
-                        Vec::new()
-                    };
-                let qualifiers = if let Some(order) =
-                    claim.get("qualifiers-order").and_then(Value::as_array)
-                {
-                    let qualifiers_json = claim
-                        .get("qualifiers")
-                        .ok_or(EntityError::QualifiersOrderButNoObject)?
-                        .as_object()
-                        .ok_or(EntityError::ExpectedObject)?;
-                    let mut qualifiers = Vec::new();
-                    for pid in order {
-                        let pid = pid.as_str().ok_or(EntityError::NoId)?;
-                        let pid_id = Pid::from_str(pid).map_err(|_| EntityError::BadId)?;
-                        let qual_list = qualifiers_json
-                            .get(pid)
-                            .and_then(Value::as_array)
-                            .ok_or(EntityError::QualiferOrderNamesNonQualifier)?;
-                        for qual in qual_list {
-                            qualifiers.push((pid_id, ClaimValueData::parse_snak(qual.clone())?));
+                if options.max_claims == Some(claims.len()) {
+                    if options.error_on_claim_limit {
+                        return Err(EntityError::ClaimLimitExceeded);
+                    }
+                    break 'claims;
+                }
+                if options.check_property_mismatch {
+                    if let Some(inner) = claim
+                        .get("mainsnak")
+                        .and_then(|snak| snak.get("property"))
+                        .and_then(Value::as_str)
+                    {
+                        let inner = Pid::from_str(inner).map_err(|_| EntityError::BadId)?;
+                        if inner != pid {
+                            return Err(EntityError::PropertyMismatch { outer: pid, inner });
                         }
                     }
-                    qualifiers
-                } else {
-                    Vec::new()
-                };
-                claims.push((
-                    pid,
-                    ClaimValue {
-                        id: claim
-                            .get("id")
-                            .ok_or(EntityError::NoClaimId)?
-                            .as_str()
-                            .ok_or(EntityError::NoClaimId)?
-                            .to_string(),
-                        rank: Rank::from_str(
-                            claim
-                                .get("rank")
-                                .ok_or(EntityError::NoRank)?
-                                .as_str()
-                                .ok_or(EntityError::NoRank)?,
-                        )?,
-                        data: ClaimValueData::parse_snak(
-                            claim
-                                .get_mut("mainsnak")
-                                .ok_or(EntityError::MissingMainsnak)?
-                                .take(),
-                        )?,
-                        qualifiers,
-                        references,
-                    },
-                ));
+                }
+                claims.push((pid, parse_claim(claim)?));
             }
         }
 
+        // lexemes from partial fetches may not have any lexeme data
+        let lexeme = if entity_type == EntityType::Lexeme && json.get("lemmas").is_some() {
+            Some(LexemeData::from_json(&mut json)?)
+        } else {
+            None
+        };
+
         Ok(Self {
             id,
             claims,
@@ -488,9 +865,318 @@ impl Entity {
             labels,
             aliases,
             sitelinks,
+            lexeme,
         })
     }
 
+    /// Convert the entity to the Wikibase JSON representation, the reverse of
+    /// [`Entity::from_json`]. This is the format used by `Special:EntityData` and the Wikibase
+    /// API (without the `entities` wrapper), so a modified entity can be sent back to Wikibase.
+    ///
+    /// Snaks are converted with [`ClaimValueData::to_snak_json`]. The datatype of a property is
+    /// taken from the values it has anywhere in the entity (in claims, qualifiers or
+    /// references); the `datatype` field is left out of no value and unknown value snaks for
+    /// properties that only have those. Qualifiers and reference claims for the same property
+    /// are grouped together, in the order each property first appears. Commons media entities
+    /// have their claims under `statements`, as on Commons, and lexemes include their
+    /// [lexeme data](Entity::lexeme). Page metadata (like `lastrevid`) isn't kept by [`Entity`],
+    /// so it isn't included.
+    ///
+    /// [`MultilingualText`](ClaimValueData::MultilingualText) values (like ones made by
+    /// [`Entity::collect_multilingual`]) have no Wikibase representation, so claims, qualifiers
+    /// and reference snaks with them are left out, along with reference groups that are left
+    /// empty. Split them into [`MonolingualText`](ClaimValueData::MonolingualText) values first
+    /// to keep them.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let json = q42.to_wikibase_json();
+    /// assert_eq!(json["labels"]["en"]["value"], "Douglas Adams");
+    /// assert_eq!(json["claims"]["P31"][0]["mainsnak"]["datavalue"]["value"]["id"], "Q5");
+    /// assert_eq!(wikidata::Entity::from_json(json).unwrap(), q42);
+    /// ```
+    #[must_use]
+    pub fn to_wikibase_json(&self) -> Value {
+        fn terms(map: &BTreeMap<Lang, String>) -> serde_json::Map<String, Value> {
+            map.iter()
+                .map(|(lang, text)| {
+                    (
+                        lang.0.clone(),
+                        serde_json::json!({ "language": lang.0, "value": text }),
+                    )
+                })
+                .collect()
+        }
+
+        // pid-grouped snaks, along with the order of the pids
+        fn snak_groups(
+            claims: &[(Pid, ClaimValueData)],
+            datatypes: &BTreeMap<Pid, Datatype>,
+        ) -> (serde_json::Map<String, Value>, Vec<Value>) {
+            let mut snaks = serde_json::Map::new();
+            let mut order = Vec::new();
+            for (pid, data) in claims {
+                let Some(snak) = snak_json(*pid, data, datatypes) else {
+                    continue;
+                };
+                let key = pid.to_string();
+                if !snaks.contains_key(&key) {
+                    order.push(Value::String(key.clone()));
+                }
+                if let Value::Array(group) =
+                    snaks.entry(key).or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    group.push(snak);
+                }
+            }
+            (snaks, order)
+        }
+
+        fn snak_json(
+            pid: Pid,
+            data: &ClaimValueData,
+            datatypes: &BTreeMap<Pid, Datatype>,
+        ) -> Option<Value> {
+            let mut snak = match (data, datatypes.get(&pid)) {
+                (ClaimValueData::MultilingualText(_), _) => return None,
+                (_, Some(datatype)) => data.to_snak_json(*datatype),
+                (ClaimValueData::NoValue, None) => serde_json::json!({ "snaktype": "novalue" }),
+                (ClaimValueData::UnknownValue, None) => {
+                    serde_json::json!({ "snaktype": "somevalue" })
+                }
+                (_, None) => return None,
+            };
+            if let Some(snak) = snak.as_object_mut() {
+                snak.insert("property".to_string(), Value::String(pid.to_string()));
+            }
+            Some(snak)
+        }
+
+        fn claims_json(
+            claims: &[(Pid, ClaimValue)],
+            datatypes: &BTreeMap<Pid, Datatype>,
+        ) -> serde_json::Map<String, Value> {
+            let mut json = serde_json::Map::new();
+            for (pid, claim) in claims {
+                let Some(mainsnak) = snak_json(*pid, &claim.data, datatypes) else {
+                    continue;
+                };
+                let mut claim_json = serde_json::json!({
+                    "mainsnak": mainsnak,
+                    "type": "statement",
+                    "id": claim.id,
+                    "rank": match claim.rank {
+                        Rank::Deprecated => "deprecated",
+                        Rank::Normal => "normal",
+                        Rank::Preferred => "preferred",
+                    },
+                });
+                let (qualifiers, order) = snak_groups(&claim.qualifiers, datatypes);
+                if !qualifiers.is_empty() {
+                    claim_json["qualifiers"] = Value::Object(qualifiers);
+                    claim_json["qualifiers-order"] = Value::Array(order);
+                }
+                let references: Vec<Value> = claim
+                    .references
+                    .iter()
+                    .filter_map(|reference| {
+                        let (snaks, order) = snak_groups(&reference.claims, datatypes);
+                        (!snaks.is_empty()).then(|| {
+                            serde_json::json!({
+                                "hash": reference.hash,
+                                "snaks": snaks,
+                                "snaks-order": order,
+                            })
+                        })
+                    })
+                    .collect();
+                if !references.is_empty() {
+                    claim_json["references"] = Value::Array(references);
+                }
+                if let Value::Array(group) = json
+                    .entry(pid.to_string())
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    group.push(claim_json);
+                }
+            }
+            json
+        }
+
+        let lexeme_claims = self.lexeme.iter().flat_map(|lexeme| {
+            let form_claims = lexeme.forms.iter().flat_map(|form| &form.claims);
+            let sense_claims = lexeme.senses.iter().flat_map(|sense| &sense.claims);
+            form_claims.chain(sense_claims)
+        });
+        let mut datatypes = BTreeMap::new();
+        for (pid, claim) in self.claims.iter().chain(lexeme_claims) {
+            let snaks =
+                std::iter::once((pid, &claim.data))
+                    .chain(claim.qualifiers.iter().map(|(pid, data)| (pid, data)))
+                    .chain(claim.references.iter().flat_map(|reference| {
+                        reference.claims.iter().map(|(pid, data)| (pid, data))
+                    }));
+            for (pid, data) in snaks {
+                if let Some(datatype) = data.datatype() {
+                    datatypes.entry(*pid).or_insert(datatype);
+                }
+            }
+        }
+
+        let aliases: serde_json::Map<String, Value> = self
+            .aliases
+            .iter()
+            .map(|(lang, aliases)| {
+                (
+                    lang.0.clone(),
+                    aliases
+                        .iter()
+                        .map(|alias| serde_json::json!({ "language": lang.0, "value": alias }))
+                        .collect(),
+                )
+            })
+            .collect();
+        let sitelinks: serde_json::Map<String, Value> = self
+            .sitelinks
+            .iter()
+            .map(|(site, sitelink)| {
+                let mut json = serde_json::json!({
+                    "site": site.0,
+                    "title": sitelink.title,
+                    "badges": sitelink.badges.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                });
+                if let Some(url) = &sitelink.url {
+                    json["url"] = Value::String(url.clone());
+                }
+                (site.0.clone(), json)
+            })
+            .collect();
+
+        let mut json = serde_json::json!({
+            "type": match self.entity_type {
+                EntityType::Entity => "item",
+                EntityType::Property => "property",
+                EntityType::Lexeme => "lexeme",
+                EntityType::MediaInfo => "mediainfo",
+            },
+            "id": self.id.to_string(),
+            "labels": terms(&self.labels),
+            "descriptions": terms(&self.descriptions),
+            "aliases": aliases,
+            "sitelinks": sitelinks,
+            "claims": claims_json(&self.claims, &datatypes),
+        });
+        if let Some(lexeme) = &self.lexeme {
+            json["lemmas"] = Value::Object(terms(&lexeme.lemmas));
+            json["language"] = Value::String(lexeme.language.to_string());
+            json["lexicalCategory"] = Value::String(lexeme.lexical_category.to_string());
+            json["forms"] = lexeme
+                .forms
+                .iter()
+                .map(|form| {
+                    serde_json::json!({
+                        "id": form.id.to_string(),
+                        "representations": terms(&form.representations),
+                        "grammaticalFeatures": form
+                            .grammatical_features
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>(),
+                        "claims": claims_json(&form.claims, &datatypes),
+                    })
+                })
+                .collect();
+            json["senses"] = lexeme
+                .senses
+                .iter()
+                .map(|sense| {
+                    serde_json::json!({
+                        "id": sense.id.to_string(),
+                        "glosses": terms(&sense.glosses),
+                        "claims": claims_json(&sense.claims, &datatypes),
+                    })
+                })
+                .collect();
+        }
+        if self.entity_type == EntityType::MediaInfo {
+            if let Some(json) = json.as_object_mut() {
+                let claims = json.remove("claims").unwrap_or_default();
+                json.insert("statements".to_string(), claims);
+            }
+        }
+        json
+    }
+
+    /// Parse newline-delimited JSON, where each non-empty line is a single entity in the
+    /// Wikibase JSON representation (as accepted by [`Entity::from_json`]). Entities are parsed
+    /// lazily as the iterator is advanced. A line that can't be parsed gives a [`DumpError`]
+    /// saying where it is, and parsing continues with the next line.
+    ///
+    /// ## Example
+    /// ```
+    /// let ndjson = r#"{"type": "item", "id": "Q1", "claims": {}}
+    /// {"type": "item", "id": "Q2", "claims": {}}
+    /// {"type": "item", "id": "Q3", "claims": []}
+    /// "#;
+    /// let entities: Vec<_> = wikidata::Entity::parse_ndjson(ndjson).collect();
+    /// assert_eq!(entities.len(), 3);
+    /// assert_eq!(entities[1].as_ref().unwrap().id, wikidata::WikiId::EntityId(wikidata::Qid(2)));
+    /// let err = entities[2].as_ref().unwrap_err();
+    /// assert_eq!(err.line, 3);
+    /// assert_eq!(err.id.as_deref(), Some("Q3"));
+    /// ```
+    pub fn parse_ndjson(ndjson: &str) -> impl Iterator<Item = Result<Self, DumpError>> + '_ {
+        ndjson
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let json: Value = serde_json::from_str(line).map_err(|_| DumpError {
+                    line: index + 1,
+                    id: None,
+                    source: EntityError::InvalidJson,
+                })?;
+                // redirects have an `entity` key instead of an `id`
+                let id = json
+                    .get("id")
+                    .or_else(|| json.get("entity"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                Self::from_json(json).map_err(|source| DumpError {
+                    line: index + 1,
+                    id,
+                    source,
+                })
+            })
+    }
+
+    /// Parse a single entity from one line of a Wikidata JSON dump, given as bytes. This is
+    /// useful along with an index of the byte offsets of each entity in a (possibly
+    /// memory-mapped) dump, to parse just the entity you need.
+    ///
+    /// The slice must contain exactly one entity in the Wikibase JSON representation. Whitespace
+    /// and a single trailing comma (as found at the end of every line but the last in a dump)
+    /// are ignored, but the surrounding `[` and `]` lines of the dump must not be included.
+    ///
+    /// ## Example
+    /// ```
+    /// let line = b"{\"type\": \"item\", \"id\": \"Q1\", \"claims\": {}},\n";
+    /// let entity = wikidata::Entity::from_dump_slice(line).unwrap();
+    /// assert_eq!(entity.id, wikidata::WikiId::EntityId(wikidata::Qid(1)));
+    /// ```
+    ///
+    /// # Errors
+    /// If the slice isn't valid JSON, [`EntityError::InvalidJson`] is returned. If the JSON can't
+    /// be parsed to an `Entity`, another `EntityError` is returned.
+    pub fn from_dump_slice(line: &[u8]) -> Result<Self, EntityError> {
+        let line = line.trim_ascii();
+        let line = line.strip_suffix(b",").unwrap_or(line);
+        let json = serde_json::from_slice(line).map_err(|_| EntityError::InvalidJson)?;
+        Self::from_json(json)
+    }
+
     /// Returns an iterator of references to all the claim values for a property ID.
     ///
     /// ## Example
@@ -505,7 +1191,6 @@ impl Entity {
     ///     }
     /// }
     /// ```
-    #[must_use]
     pub fn pid_claims(&self, pid: Pid) -> impl Iterator<Item = &ClaimValue> {
         self.claims
             .iter()
@@ -513,25 +1198,1176 @@ impl Entity {
             .map(|(_, value)| value)
     }
 
-    /// Find a claim by its ID.
+    /// Get the `index`th claim value for a property ID, counting from zero, in the order the
+    /// claims appear in [`Entity::claims`].
     ///
     /// ## Example
-    ///
     /// ```
     /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
     /// # let q42 = wikidata::Entity::from_json(j).unwrap();
-    /// let (pid, claim_value) = q42.claim_by_id("Q42$285E0C13-9674-4131-9556-51B316A57AEE").unwrap();
-    /// assert_eq!(pid, wikidata::Pid(1411));
-    /// assert_eq!(claim_value.rank, wikidata::Rank::Normal);
+    /// let height = q42.claim_value(wikidata::consts::HEIGHT, 0).unwrap();
+    /// assert!(matches!(height.data, wikidata::ClaimValueData::Quantity { .. }));
+    /// assert!(q42.claim_value(wikidata::consts::HEIGHT, 1).is_none());
     /// ```
     #[must_use]
-    pub fn claim_by_id(&self, id: &str) -> Option<(Pid, &ClaimValue)> {
-        self.claims
-            .iter()
-            .find(|(_, value)| value.id == id)
-            .map(|(pid, value)| (*pid, value))
+    pub fn claim_value(&self, pid: Pid, index: usize) -> Option<&ClaimValue> {
+        self.pid_claims(pid).nth(index)
     }
-}
+
+    /// The best statement for a property ID, along with all of its qualifiers and references.
+    /// This is the first (in the order of [`Entity::claims`]) of the statements with the highest
+    /// rank, so preferred statements are picked over normal ones. Deprecated statements are
+    /// never picked.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let birth = q42.best_statement(wikidata::consts::DATE_OF_BIRTH).unwrap();
+    /// assert!(!birth.references.is_empty());
+    /// ```
+    #[must_use]
+    pub fn best_statement(&self, pid: Pid) -> Option<&ClaimValue> {
+        self.best_claims(pid).into_iter().next()
+    }
+
+    /// All of the statements for a property ID with the highest rank, in the order of
+    /// [`Entity::claims`]. These are the preferred statements if there are any, or otherwise the
+    /// normal ones. Deprecated statements are never included, so this is empty if there are only
+    /// deprecated statements.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+    /// # let portugal = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, Rank};
+    /// let populations = portugal.best_claims(consts::POPULATION);
+    /// assert_eq!(populations.len(), 1);
+    /// assert_eq!(populations[0].rank, Rank::Preferred);
+    /// ```
+    #[must_use]
+    pub fn best_claims(&self, pid: Pid) -> Vec<&ClaimValue> {
+        best_rank_claims(
+            self.claims
+                .iter()
+                .filter(|(claim_pid, _)| *claim_pid == pid),
+        )
+        .map(|(_, claim)| claim)
+        .collect()
+    }
+
+    /// The statement for a property ID with the most recent time in a qualifier, like the
+    /// current population of a city using its [point in time](consts::POINT_IN_TIME) qualifiers.
+    /// This is useful when the most recent statement isn't marked as preferred. If several
+    /// statements have the most recent time, the one with the highest rank is picked, and then
+    /// the first one. Deprecated statements are never picked.
+    ///
+    /// Returns `None` if none of the statements have a time for the qualifier; use
+    /// [`Entity::best_statement`] as a fallback if you need a value either way.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+    /// # let portugal = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, ClaimValueData};
+    /// let population = portugal.latest_value(consts::POPULATION, consts::POINT_IN_TIME).unwrap();
+    /// let ClaimValueData::Quantity { amount, .. } = population.data else { panic!() };
+    /// assert_eq!(amount, 10_295_909.);
+    /// ```
+    #[must_use]
+    pub fn latest_value(&self, pid: Pid, time_qualifier: Pid) -> Option<&ClaimValue> {
+        self.pid_claims(pid)
+            .filter(|claim| claim.rank != Rank::Deprecated)
+            .filter_map(|claim| Some((claim.qualifier_time(time_qualifier)?, claim)))
+            .fold(
+                None,
+                |latest: Option<(DateTime<Utc>, &ClaimValue)>, (time, claim)| match latest {
+                    Some((latest_time, latest))
+                        if (latest_time, latest.rank) >= (time, claim.rank) =>
+                    {
+                        Some((latest_time, latest))
+                    }
+                    _ => Some((time, claim)),
+                },
+            )
+            .map(|(_, claim)| claim)
+    }
+
+    /// Find a claim by its ID.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let (pid, claim_value) = q42.claim_by_id("Q42$285E0C13-9674-4131-9556-51B316A57AEE").unwrap();
+    /// assert_eq!(pid, wikidata::Pid(1411));
+    /// assert_eq!(claim_value.rank, wikidata::Rank::Normal);
+    /// ```
+    #[must_use]
+    pub fn claim_by_id(&self, id: &str) -> Option<(Pid, &ClaimValue)> {
+        self.claims
+            .iter()
+            .find(|(_, value)| value.id == id)
+            .map(|(pid, value)| (*pid, value))
+    }
+
+    /// Remove all of the claims for a property ID, returning the removed claim values in the
+    /// order they were in.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let mut q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let removed = q42.remove_claims(wikidata::Pid(69));
+    /// assert_eq!(removed.len(), 2);
+    /// assert_eq!(q42.pid_claims(wikidata::Pid(69)).count(), 0);
+    /// ```
+    pub fn remove_claims(&mut self, pid: Pid) -> Vec<ClaimValue> {
+        let (removed, kept) = std::mem::take(&mut self.claims)
+            .into_iter()
+            .partition(|(claim_pid, _)| *claim_pid == pid);
+        self.claims = kept;
+        removed.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Add a claim for a property ID, after all of the existing claims.
+    pub fn add_claim(&mut self, pid: Pid, value: ClaimValue) {
+        self.claims.push((pid, value));
+    }
+
+    /// The distinct properties on the entity that have
+    /// [external identifier](ClaimValueData::ExternalID) values, like VIAF or GND IDs, in the
+    /// order they first appear in [`Entity::claims`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let props = q42.external_id_properties();
+    /// assert!(props.contains(&wikidata::Pid(214))); // VIAF ID
+    /// assert!(!props.contains(&wikidata::consts::INSTANCE_OF));
+    /// ```
+    #[must_use]
+    pub fn external_id_properties(&self) -> Vec<Pid> {
+        let mut props = Vec::new();
+        for (pid, claim) in &self.claims {
+            if matches!(claim.data, ClaimValueData::ExternalID(_)) && !props.contains(pid) {
+                props.push(*pid);
+            }
+        }
+        props
+    }
+
+    /// Links to the entity's external identifiers, for the properties that have a
+    /// [known formatter URL](consts::identifier_formatter_url), in the order of
+    /// [`Entity::claims`]. Deprecated identifiers are left out. Identifiers are percent-encoded
+    /// before they're put in the URL, like on Wikidata, except for some punctuation like `/`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::consts;
+    /// let urls = q42.external_id_urls();
+    /// assert!(urls.contains(&(consts::VIAF_ID, "https://viaf.org/viaf/113230702".to_string())));
+    /// ```
+    #[must_use]
+    pub fn external_id_urls(&self) -> Vec<(Pid, String)> {
+        self.claims
+            .iter()
+            .filter(|(_, claim)| claim.rank != Rank::Deprecated)
+            .filter_map(|(pid, claim)| match &claim.data {
+                ClaimValueData::ExternalID(id) => Some((
+                    *pid,
+                    consts::identifier_formatter_url(*pid)?.replace("$1", &encode_url_part(id)),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All of the claims that aren't [referenced](ClaimValue::is_referenced), in the order of
+    /// [`Entity::claims`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let unreferenced = q42.unreferenced_claims();
+    /// assert!(unreferenced.iter().all(|(_, claim)| claim.references.is_empty()));
+    /// ```
+    #[must_use]
+    pub fn unreferenced_claims(&self) -> Vec<(Pid, &ClaimValue)> {
+        self.claims
+            .iter()
+            .filter(|(_, claim)| !claim.is_referenced())
+            .map(|(pid, claim)| (*pid, claim))
+            .collect()
+    }
+
+    /// Every distinct reference group used by the entity's claims, in the order they first
+    /// appear. The same reference group is often used by many claims; reference groups are the
+    /// same if they have the same hash.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let references = q42.distinct_references();
+    /// let total: usize = q42.claims.iter().map(|(_, claim)| claim.references.len()).sum();
+    /// assert!(references.len() < total);
+    /// ```
+    #[must_use]
+    pub fn distinct_references(&self) -> Vec<&ReferenceGroup> {
+        let mut seen = BTreeSet::new();
+        self.claims
+            .iter()
+            .flat_map(|(_, claim)| &claim.references)
+            .filter(|reference| seen.insert(reference.hash.as_str()))
+            .collect()
+    }
+
+    /// Every reference group on the entity's claims, along with the property and claim it's for,
+    /// in the order of [`Entity::claims`]. Unlike [`Entity::distinct_references`], a reference
+    /// group used by many claims is yielded once for each of them.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::consts;
+    /// let urls = q42
+    ///     .all_references()
+    ///     .filter_map(|(_, _, reference)| reference.claim_for(consts::REFERENCE_URL));
+    /// assert!(urls.count() > 0);
+    /// ```
+    pub fn all_references(&self) -> impl Iterator<Item = (Pid, &ClaimValue, &ReferenceGroup)> {
+        self.claims.iter().flat_map(|(pid, claim)| {
+            claim
+                .references
+                .iter()
+                .map(move |reference| (*pid, claim, reference))
+        })
+    }
+
+    /// All of the claims that use the reference group with a hash, such as one from
+    /// [`Entity::distinct_references`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// for reference in q42.distinct_references() {
+    ///     assert!(!q42.claims_with_reference(&reference.hash).is_empty());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn claims_with_reference(&self, hash: &str) -> Vec<(Pid, &ClaimValue)> {
+        self.claims
+            .iter()
+            .filter(|(_, claim)| {
+                claim
+                    .references
+                    .iter()
+                    .any(|reference| reference.hash == hash)
+            })
+            .map(|(pid, claim)| (*pid, claim))
+            .collect()
+    }
+
+    /// Group all of the claims by property ID. Claims for the same property stay in the order
+    /// they appear in [`Entity::claims`].
+    ///
+    /// The map borrows the claim values from `self`, so it can't outlive the entity. If you need
+    /// to do a lot of lookups by property, build this once and reuse it instead of calling
+    /// [`Entity::pid_claims`] repeatedly.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let claims = q42.claims_map();
+    /// assert_eq!(claims[&wikidata::Pid(69)].len(), 2);
+    /// ```
+    #[must_use]
+    pub fn claims_map(&self) -> BTreeMap<Pid, Vec<&ClaimValue>> {
+        let mut map: BTreeMap<Pid, Vec<&ClaimValue>> = BTreeMap::new();
+        for (pid, value) in &self.claims {
+            map.entry(*pid).or_default().push(value);
+        }
+        map
+    }
+
+    /// All of the non-deprecated [`Item`](ClaimValueData::Item) values for a property ID.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.item_values(wikidata::consts::INSTANCE_OF), vec![wikidata::consts::HUMAN]);
+    /// ```
+    #[must_use]
+    pub fn item_values(&self, pid: Pid) -> Vec<Qid> {
+        self.pid_claims(pid)
+            .filter(|claim| claim.rank != Rank::Deprecated)
+            .filter_map(|claim| match claim.data {
+                ClaimValueData::Item(qid) => Some(qid),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The [`Item`](ClaimValueData::Item) value of the first [best](Entity::best_claims) claim
+    /// for a property ID that has one.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::consts;
+    /// assert_eq!(q42.get_item(consts::INSTANCE_OF), Some(consts::HUMAN));
+    /// assert_eq!(q42.get_item(consts::DATE_OF_BIRTH), None);
+    /// ```
+    #[must_use]
+    pub fn get_item(&self, pid: Pid) -> Option<Qid> {
+        self.best_claims(pid)
+            .into_iter()
+            .find_map(|claim| match claim.data {
+                ClaimValueData::Item(qid) => Some(qid),
+                _ => None,
+            })
+    }
+
+    /// The [`String`](ClaimValueData::String) value of the first [best](Entity::best_claims)
+    /// claim for a property ID that has one. Other kinds of text, like external identifiers and
+    /// URLs, aren't included.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// // Commons category
+    /// assert_eq!(q42.get_string(wikidata::Pid(373)), Some("Douglas Adams"));
+    /// ```
+    #[must_use]
+    pub fn get_string(&self, pid: Pid) -> Option<&str> {
+        self.best_claims(pid)
+            .into_iter()
+            .find_map(|claim| match &claim.data {
+                ClaimValueData::String(string) => Some(string.as_str()),
+                _ => None,
+            })
+    }
+
+    /// The amount of the first [best](Entity::best_claims) claim for a property ID that is a
+    /// [`Quantity`](ClaimValueData::Quantity). The unit is ignored; use
+    /// [`ClaimValueData::quantity_si`] if it's needed.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.get_quantity(wikidata::consts::HEIGHT), Some(1.96));
+    /// ```
+    #[must_use]
+    pub fn get_quantity(&self, pid: Pid) -> Option<f64> {
+        self.best_claims(pid)
+            .into_iter()
+            .find_map(|claim| match claim.data {
+                ClaimValueData::Quantity { amount, .. } => Some(amount),
+                _ => None,
+            })
+    }
+
+    /// The [date and time](ClaimValueData::as_datetime) of the first [best](Entity::best_claims)
+    /// claim for a property ID that is a [`DateTime`](ClaimValueData::DateTime).
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///     q42.get_datetime(wikidata::consts::DATE_OF_BIRTH),
+    ///     Some(Utc.with_ymd_and_hms(1952, 3, 11, 0, 0, 0).unwrap()),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn get_datetime(&self, pid: Pid) -> Option<DateTime<Utc>> {
+        self.best_claims(pid)
+            .into_iter()
+            .find_map(|claim| claim.data.as_datetime())
+    }
+
+    /// All of the non-deprecated [`Url`](ClaimValueData::Url) values for a property ID.
+    #[must_use]
+    pub fn url_values(&self, pid: Pid) -> Vec<&str> {
+        self.pid_claims(pid)
+            .filter(|claim| claim.rank != Rank::Deprecated)
+            .filter_map(|claim| match &claim.data {
+                ClaimValueData::Url(url) => Some(url.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// URLs of pages describing the entity, from [described at URL](consts::DESCRIBED_AT_URL).
+    #[must_use]
+    pub fn described_at_urls(&self) -> Vec<&str> {
+        self.url_values(consts::DESCRIBED_AT_URL)
+    }
+
+    /// Works describing the entity, from [described by source](consts::DESCRIBED_BY_SOURCE).
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert!(!q42.described_by_sources().is_empty());
+    /// ```
+    #[must_use]
+    pub fn described_by_sources(&self) -> Vec<Qid> {
+        self.item_values(consts::DESCRIBED_BY_SOURCE)
+    }
+
+    /// The countries the entity is in, from [country](consts::COUNTRY).
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    /// # let q513 = wikidata::Entity::from_json(j).unwrap();
+    /// // Mount Everest is on the border of Nepal and China
+    /// assert!(q513.countries().contains(&wikidata::Qid(837)));
+    /// assert!(q513.countries().contains(&wikidata::Qid(148)));
+    /// ```
+    #[must_use]
+    pub fn countries(&self) -> Vec<Qid> {
+        self.item_values(consts::COUNTRY)
+    }
+
+    /// The administrative divisions the entity is directly located in, from
+    /// [located in the administrative territorial entity](consts::LOCATED_IN_ADMINISTRATIVE_TERRITORIAL_ENTITY).
+    /// These are usually the smallest divisions containing the entity; follow the same property
+    /// on them to build a full hierarchy.
+    #[must_use]
+    pub fn located_in(&self) -> Vec<Qid> {
+        self.item_values(consts::LOCATED_IN_ADMINISTRATIVE_TERRITORIAL_ENTITY)
+    }
+
+    /// What the entity is a part of, from [part of](consts::PART_OF).
+    #[must_use]
+    pub fn part_of(&self) -> Vec<Qid> {
+        self.item_values(consts::PART_OF)
+    }
+
+    /// The sitelink to a site, given a site key like `enwiki`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let sitelink = q42.sitelink("be_x_oldwiki").unwrap();
+    /// assert_eq!(sitelink.title, "Дуглас Адамз");
+    /// assert!(sitelink.url.as_deref().unwrap().starts_with("https://be-tarask.wikipedia.org/"));
+    /// assert!(q42.sitelink("notasitewiki").is_none());
+    /// ```
+    #[must_use]
+    pub fn sitelink(&self, site: &str) -> Option<&SitelinkValue> {
+        self.sitelinks.get(&SiteName(site.to_string()))
+    }
+
+    /// The title of the page linked to on a site, given a site key like `enwiki`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.sitelink_title("enwiki"), Some("Douglas Adams"));
+    /// assert_eq!(q42.sitelink_title("notasitewiki"), None);
+    /// ```
+    #[must_use]
+    pub fn sitelink_title(&self, site: &str) -> Option<&str> {
+        self.sitelink(site).map(|sitelink| sitelink.title.as_str())
+    }
+
+    /// The URL of the entity's Wikipedia article in a language, given the language's Wikipedia
+    /// subdomain like `en` or `zh-min-nan`. Returns `None` if there's no sitelink to that
+    /// Wikipedia.
+    ///
+    /// The sitelink for a subdomain is usually the subdomain with hyphens replaced by
+    /// underscores followed by `wiki`, so `zh-min-nan` uses `zh_min_nanwiki`. The exception is
+    /// `be-tarask`, which uses `be_x_oldwiki`; `be-x-old` is accepted for it as well. The title is
+    /// encoded the same way Wikipedia encodes titles in its URLs, with spaces as underscores.
+    ///
+    /// Some other Wikimedia projects also have sitelinks ending in `wiki`, like `commonswiki` for
+    /// Wikimedia Commons. Those aren't Wikipedias, so `None` is returned for them.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(
+    ///     q42.wikipedia_url("en").as_deref(),
+    ///     Some("https://en.wikipedia.org/wiki/Douglas_Adams"),
+    /// );
+    /// assert_eq!(
+    ///     q42.wikipedia_url("ru").as_deref(),
+    ///     Some("https://ru.wikipedia.org/wiki/%D0%90%D0%B4%D0%B0%D0%BC%D1%81,_%D0%94%D1%83%D0%B3%D0%BB%D0%B0%D1%81"),
+    /// );
+    /// assert_eq!(
+    ///     q42.wikipedia_url("be-tarask").as_deref(),
+    ///     Some("https://be-tarask.wikipedia.org/wiki/%D0%94%D1%83%D0%B3%D0%BB%D0%B0%D1%81_%D0%90%D0%B4%D0%B0%D0%BC%D0%B7"),
+    /// );
+    /// assert_eq!(q42.wikipedia_url("notalanguage"), None);
+    /// assert_eq!(q42.wikipedia_url("commons"), None);
+    /// ```
+    #[must_use]
+    pub fn wikipedia_url(&self, lang: &str) -> Option<String> {
+        // sites that use the `wiki` suffix, but aren't Wikipedias
+        const NON_WIKIPEDIAS: &[&str] = &[
+            "commons",
+            "foundation",
+            "incubator",
+            "mediawiki",
+            "meta",
+            "outreach",
+            "sources",
+            "species",
+            "test",
+            "test2",
+            "testwikidata",
+            "wikidata",
+            "wikifunctions",
+            "wikimania",
+        ];
+        if NON_WIKIPEDIAS.contains(&lang) {
+            return None;
+        }
+        let (site, subdomain) = match lang {
+            "be-tarask" | "be-x-old" => ("be_x_oldwiki".to_string(), "be-tarask".to_string()),
+            _ => (
+                format!("{}wiki", lang.replace('-', "_")),
+                lang.replace('_', "-"),
+            ),
+        };
+        let title = self.sitelink_title(&site)?;
+        Some(format!(
+            "https://{subdomain}.wikipedia.org/wiki/{}",
+            encode_title(title)
+        ))
+    }
+
+    /// Get the label of the entity in a language, given as a language code like `en`. Only
+    /// labels in exactly that language are returned; see [`Entity::label_with_fallback`] for
+    /// falling back to other languages.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.label("en"), Some("Douglas Adams"));
+    /// assert_eq!(q42.label("xx"), None);
+    /// ```
+    #[must_use]
+    pub fn label(&self, lang: &str) -> Option<&str> {
+        self.labels.get(&Lang(lang.to_string())).map(String::as_str)
+    }
+
+    /// Get the description of the entity in a language, given as a language code like `en`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.description("en"), Some("English writer and humorist"));
+    /// ```
+    #[must_use]
+    pub fn description(&self, lang: &str) -> Option<&str> {
+        self.descriptions
+            .get(&Lang(lang.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Get the label of the entity in the first of `langs` it has a label in, like a user's
+    /// locale followed by English. If there's no label in any of them, the label in `mul` (for
+    /// names that are the same in multiple languages) is used, if there is one. Blank labels (like
+    /// ones set in code) are skipped, as if they weren't there.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert_eq!(q42.label_with_fallback(&["xx", "de", "en"]), Some("Douglas Adams"));
+    /// assert_eq!(q42.label_with_fallback(&["xx"]), None);
+    /// ```
+    #[must_use]
+    pub fn label_with_fallback(&self, langs: &[&str]) -> Option<&str> {
+        term_with_fallback(&self.labels, langs)
+    }
+
+    /// Get some label of the entity, in any language. This is useful when any human-readable
+    /// name will do, like when logging. It's deterministic: since labels are stored in a
+    /// `BTreeMap`, it's always the label with the alphabetically first language code.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q106975887.json")).unwrap();
+    /// # let entity = wikidata::Entity::from_json(j).unwrap();
+    /// // "af" is the first language with a label
+    /// assert_eq!(entity.any_label(), Some("Marinette Yetna"));
+    /// ```
+    #[must_use]
+    pub fn any_label(&self) -> Option<&str> {
+        self.labels.values().next().map(String::as_str)
+    }
+
+    /// All of the names the entity is known by in a language: its label (if it has one),
+    /// followed by its aliases (if it has any).
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q106975887.json")).unwrap();
+    /// # let entity = wikidata::Entity::from_json(j).unwrap();
+    /// let names = entity.names_in(&wikidata::Lang::en());
+    /// assert_eq!(names, vec!["Marinette Yetna", "Mbeleg Yetna Marinette"]);
+    /// ```
+    #[must_use]
+    pub fn names_in(&self, lang: &Lang) -> Vec<&str> {
+        self.labels
+            .get(lang)
+            .into_iter()
+            .chain(self.aliases.get(lang).into_iter().flatten())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// All of the labels that contain `needle`, ignoring case, along with their languages. This
+    /// is useful for showing why an entity matched a search. Use [`Entity::aliases_matching`] to
+    /// search aliases too.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let matches = q42.labels_matching("ДУГЛАС");
+    /// assert!(matches.contains(&(&wikidata::Lang("ru".to_string()), "Дуглас Адамс")));
+    /// assert!(matches.iter().all(|(_, label)| label.to_lowercase().contains("дуглас")));
+    /// ```
+    #[must_use]
+    pub fn labels_matching(&self, needle: &str) -> Vec<(&Lang, &str)> {
+        let needle = needle.to_lowercase();
+        self.labels
+            .iter()
+            .filter(|(_, label)| label.to_lowercase().contains(&needle))
+            .map(|(lang, label)| (lang, label.as_str()))
+            .collect()
+    }
+
+    /// All of the aliases that contain `needle`, ignoring case, along with their languages. See
+    /// [`Entity::labels_matching`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let matches = q42.aliases_matching("noel");
+    /// assert!(matches.contains(&(&wikidata::Lang("en".to_string()), "Douglas Noel Adams")));
+    /// ```
+    #[must_use]
+    pub fn aliases_matching(&self, needle: &str) -> Vec<(&Lang, &str)> {
+        let needle = needle.to_lowercase();
+        self.aliases
+            .iter()
+            .flat_map(|(lang, aliases)| aliases.iter().map(move |alias| (lang, alias.as_str())))
+            .filter(|(_, alias)| alias.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    /// Collect all of the text values of a property with monolingual text values (like
+    /// [official name](consts::OFFICIAL_NAME)), grouped by language. Both
+    /// [`MonolingualText`](ClaimValueData::MonolingualText) and
+    /// [`MultilingualText`](ClaimValueData::MultilingualText) values are included. Deprecated
+    /// claims are skipped.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let names = q42.monolingual_values(wikidata::Pid(1477));
+    /// assert_eq!(names[&wikidata::Lang("en".to_string())], vec!["Douglas Noël Adams"]);
+    /// ```
+    #[must_use]
+    pub fn monolingual_values(&self, pid: Pid) -> BTreeMap<Lang, Vec<String>> {
+        let mut map: BTreeMap<Lang, Vec<String>> = BTreeMap::new();
+        for claim in self.pid_claims(pid) {
+            if claim.rank == Rank::Deprecated {
+                continue;
+            }
+            let texts = match &claim.data {
+                ClaimValueData::MonolingualText(text) => std::slice::from_ref(text),
+                ClaimValueData::MultilingualText(texts) => &texts[..],
+                _ => continue,
+            };
+            for text in texts {
+                map.entry(text.lang.clone())
+                    .or_default()
+                    .push(text.text.clone());
+            }
+        }
+        map
+    }
+
+    /// Remove the references and qualifiers from every claim (including the claims on the forms
+    /// and senses of lexemes), to save memory when they aren't
+    /// needed anymore. Everything else, including the claims' data, ranks, and IDs, is kept.
+    pub fn strip_references(&mut self) {
+        let lexeme_claims = self.lexeme.iter_mut().flat_map(|lexeme| {
+            let form_claims = lexeme.forms.iter_mut().flat_map(|form| &mut form.claims);
+            let sense_claims = lexeme.senses.iter_mut().flat_map(|sense| &mut sense.claims);
+            form_claims.chain(sense_claims)
+        });
+        for (_, claim) in self.claims.iter_mut().chain(lexeme_claims) {
+            claim.references = Vec::new();
+            claim.qualifiers = Vec::new();
+        }
+    }
+
+    /// Get a copy of the entity without any references or qualifiers on its claims. See
+    /// [`Entity::strip_references`] for exactly what is removed.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let stripped = q42.stripped();
+    /// assert_eq!(stripped.claims.len(), q42.claims.len());
+    /// assert!(stripped.claims.iter().all(|(_, claim)| claim.references.is_empty()));
+    /// ```
+    #[must_use]
+    pub fn stripped(&self) -> Self {
+        let mut stripped = Self {
+            claims: self
+                .claims
+                .iter()
+                .map(|(pid, claim)| {
+                    (
+                        *pid,
+                        ClaimValue {
+                            data: claim.data.clone(),
+                            rank: claim.rank,
+                            id: claim.id.clone(),
+                            qualifiers: Vec::new(),
+                            references: Vec::new(),
+                        },
+                    )
+                })
+                .collect(),
+            ..self.clone_without_claims()
+        };
+        // the claims of lexeme forms and senses are copied as-is, so strip them too
+        stripped.strip_references();
+        stripped
+    }
+
+    /// Get a copy of the entity with only the claims that match a predicate, which is given each
+    /// claim's property ID and value. Everything other than the claims (labels, descriptions,
+    /// aliases, and sitelinks) is kept intact.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::consts;
+    /// let keep = [consts::INSTANCE_OF, consts::DATE_OF_BIRTH];
+    /// let trimmed = q42.filter_claims(|pid, _| keep.contains(&pid));
+    /// assert_eq!(trimmed.claims.len(), 2);
+    /// assert_eq!(trimmed.labels, q42.labels);
+    /// ```
+    #[must_use]
+    pub fn filter_claims<F: Fn(Pid, &ClaimValue) -> bool>(&self, f: F) -> Self {
+        Self {
+            claims: self
+                .claims
+                .iter()
+                .filter(|(pid, claim)| f(*pid, claim))
+                .cloned()
+                .collect(),
+            ..self.clone_without_claims()
+        }
+    }
+
+    fn clone_without_claims(&self) -> Self {
+        Self {
+            id: self.id,
+            claims: Vec::new(),
+            entity_type: self.entity_type,
+            descriptions: self.descriptions.clone(),
+            labels: self.labels.clone(),
+            aliases: self.aliases.clone(),
+            sitelinks: self.sitelinks.clone(),
+            lexeme: self.lexeme.clone(),
+        }
+    }
+
+    /// Combine all of the [`MonolingualText`](ClaimValueData::MonolingualText) values of a
+    /// property into a single [`MultilingualText`](ClaimValueData::MultilingualText), in the
+    /// order the claims appear. Deprecated claims are skipped.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{ClaimValueData, Lang, Text};
+    /// assert_eq!(
+    ///     q42.collect_multilingual(wikidata::Pid(1477)),
+    ///     ClaimValueData::MultilingualText(vec![Text {
+    ///         text: "Douglas Noël Adams".to_string(),
+    ///         lang: Lang("en".to_string()),
+    ///     }]),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn collect_multilingual(&self, pid: Pid) -> ClaimValueData {
+        ClaimValueData::MultilingualText(
+            self.pid_claims(pid)
+                .filter(|claim| claim.rank != Rank::Deprecated)
+                .filter_map(|claim| match &claim.data {
+                    ClaimValueData::MonolingualText(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Get a flat record of the entity's ID, its label in a language, and the
+    /// [best value](Entity::best_statement) of each of some properties, all as strings. This is
+    /// useful for exporting entities to a table, like a CSV file.
+    ///
+    /// Values are rendered as strings like so:
+    ///
+    /// | value | rendering |
+    /// | ----- | --------- |
+    /// | text-like values (strings, URLs, external IDs, media, ...) | the text as-is |
+    /// | items, properties, lexemes, forms, senses | the ID, like `Q42` |
+    /// | monolingual text | the text, without the language |
+    /// | multilingual text | each text, separated by `; ` |
+    /// | coordinates | `latitude,longitude` |
+    /// | quantities | the amount, followed by the unit: its [suffix](Qid::unit_suffix) if there is one, otherwise its ID (or IRI) after a space |
+    /// | times | an ISO 8601 date, truncated to the year or month if the precision is lower than a day |
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, Lang};
+    /// let record = q42.to_flat_record(
+    ///     &[consts::INSTANCE_OF, consts::HEIGHT, consts::DATE_OF_BIRTH],
+    ///     &Lang::en(),
+    /// );
+    /// assert_eq!(record.id, "Q42");
+    /// assert_eq!(record.label.as_deref(), Some("Douglas Adams"));
+    /// assert_eq!(record.values[0].1.as_deref(), Some("Q5"));
+    /// assert_eq!(record.values[1].1.as_deref(), Some("1.96 m"));
+    /// assert_eq!(record.values[2].1.as_deref(), Some("1952-03-11"));
+    /// ```
+    #[must_use]
+    pub fn to_flat_record(&self, props: &[Pid], lang: &Lang) -> FlatRecord {
+        FlatRecord {
+            id: self.id.to_string(),
+            label: self.labels.get(lang).cloned(),
+            values: props
+                .iter()
+                .map(|pid| {
+                    (
+                        *pid,
+                        self.best_statement(*pid)
+                            .and_then(|claim| claim.data.to_display_string()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert the entity to a simple JSON object meant for humans to read, like when debugging
+    /// or exploring data. This is different from both the Wikibase JSON representation and this
+    /// crate's serialization format, and isn't meant to be parsed back.
+    ///
+    /// The object has the entity's `id`, its `label` in `lang` (or `null`), and its `claims`, as
+    /// an object mapping each property to a list of its values. Deprecated claims are left out.
+    /// Properties and item values that have a [constant](crate::consts) in this crate are
+    /// written as the constant's name in lowercase (like `instance of` or `human`, with
+    /// abbreviated names spelled out, like `facebook id`), and as their ID otherwise. Other values are written as strings like in [`Entity::to_flat_record`], and
+    /// unknown or no values are written as `null`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let json = q42.to_simple_json(&wikidata::Lang::en());
+    /// assert_eq!(json["id"], "Q42");
+    /// assert_eq!(json["label"], "Douglas Adams");
+    /// assert_eq!(json["claims"]["instance of"][0], "human");
+    /// assert_eq!(json["claims"]["height"][0], "1.96 m");
+    /// assert_eq!(json["claims"]["P800"][0], "Q25169");
+    /// ```
+    #[must_use]
+    pub fn to_simple_json(&self, lang: &Lang) -> Value {
+        let mut claims: BTreeMap<String, Vec<Option<String>>> = BTreeMap::new();
+        for (pid, claim) in &self.claims {
+            if claim.rank == Rank::Deprecated {
+                continue;
+            }
+            let value = match &claim.data {
+                ClaimValueData::Item(qid) => Some(simple_qid_name(*qid)),
+                ClaimValueData::Property(pid) => Some(simple_pid_name(*pid)),
+                data => data.to_display_string(),
+            };
+            claims.entry(simple_pid_name(*pid)).or_default().push(value);
+        }
+        serde_json::json!({
+            "id": self.id.to_string(),
+            "label": self.labels.get(lang),
+            "claims": claims,
+        })
+    }
+
+    /// Put the entity into a canonical form, so that two entities with the same data serialize
+    /// identically regardless of the order their claims were parsed or added in. This:
+    ///
+    /// - sorts claims by property ID, then by claim ID;
+    /// - sorts the qualifiers of each claim by property ID, keeping qualifiers with the same
+    ///   property in their existing order;
+    /// - sorts the reference groups of each claim by hash, and the claims inside each reference
+    ///   group by property ID, again keeping claims with the same property in their existing
+    ///   order;
+    /// - sorts the aliases for each language.
+    ///
+    /// Labels, descriptions, and sitelinks are stored in `BTreeMap`s so they are already in a
+    /// canonical order.
+    pub fn canonicalize(&mut self) {
+        self.claims
+            .sort_by(|(pid_a, a), (pid_b, b)| pid_a.cmp(pid_b).then_with(|| a.id.cmp(&b.id)));
+        for (_, claim) in &mut self.claims {
+            claim.qualifiers.sort_by_key(|(pid, _)| *pid);
+            claim.references.sort_by(|a, b| a.hash.cmp(&b.hash));
+            for reference in &mut claim.references {
+                reference.claims.sort_by_key(|(pid, _)| *pid);
+            }
+        }
+        for aliases in self.aliases.values_mut() {
+            aliases.sort();
+        }
+    }
+
+    /// The first non-deprecated [coordinate location](consts::COORDINATE_LOCATION) of the entity
+    /// on [Earth](consts::EARTH), as `(latitude, longitude)`.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    /// # let q513 = wikidata::Entity::from_json(j).unwrap();
+    /// let (lat, lon) = q513.coordinate_location().unwrap();
+    /// assert_eq!(lat, 27.988055555556);
+    /// assert_eq!(lon, 86.925277777778);
+    /// ```
+    #[must_use]
+    pub fn coordinate_location(&self) -> Option<(f64, f64)> {
+        self.pid_claims(consts::COORDINATE_LOCATION)
+            .filter(|claim| claim.rank != Rank::Deprecated)
+            .find_map(|claim| match &claim.data {
+                ClaimValueData::GlobeCoordinate {
+                    lat, lon, globe, ..
+                } if *globe == consts::EARTH => Some((*lat, *lon)),
+                _ => None,
+            })
+    }
+
+    /// Whether two entities have the same meaningful data, for detecting whether an entity's
+    /// content changed between two fetches. This is like `==`, except that:
+    ///
+    /// - the order of claims, including the claims on the forms and senses of lexemes, doesn't
+    ///   matter (but the order of the qualifiers and reference groups of each claim does);
+    /// - claim IDs are ignored;
+    /// - reference group hashes are ignored.
+    ///
+    /// Everything else, including the entity's ID and type, the rank of each claim, and each
+    /// sitelink's badges and URL, must be the same. Revision metadata like `lastrevid` and
+    /// `modified` isn't parsed into an `Entity`, so it never affects this.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let mut other = q42.clone();
+    /// other.claims.reverse();
+    /// other.claims[0].1.id = "Q42$something-else".to_string();
+    /// assert!(q42.data_eq(&other));
+    /// other.labels.clear();
+    /// assert!(!q42.data_eq(&other));
+    /// ```
+    #[must_use]
+    pub fn data_eq(&self, other: &Entity) -> bool {
+        fn claim_data_eq(a: &ClaimValue, b: &ClaimValue) -> bool {
+            a.data == b.data
+                && a.rank == b.rank
+                && a.qualifiers == b.qualifiers
+                && a.references.len() == b.references.len()
+                && a.references
+                    .iter()
+                    .zip(&b.references)
+                    .all(|(a, b)| a.claims == b.claims)
+        }
+
+        fn claims_data_eq(a: &[(Pid, ClaimValue)], b: &[(Pid, ClaimValue)]) -> bool {
+            if a.len() != b.len() {
+                return false;
+            }
+            // match each claim with a different equal claim of the other entity
+            let mut used = vec![false; b.len()];
+            a.iter().all(|(pid, claim)| {
+                let found = b.iter().enumerate().find(|(i, (other_pid, other))| {
+                    !used[*i] && pid == other_pid && claim_data_eq(claim, other)
+                });
+                if let Some((i, _)) = found {
+                    used[i] = true;
+                    true
+                } else {
+                    false
+                }
+            })
+        }
+
+        fn lexeme_data_eq(a: &LexemeData, b: &LexemeData) -> bool {
+            a.lemmas == b.lemmas
+                && a.language == b.language
+                && a.lexical_category == b.lexical_category
+                && a.forms.len() == b.forms.len()
+                && a.forms.iter().zip(&b.forms).all(|(a, b)| {
+                    a.id == b.id
+                        && a.representations == b.representations
+                        && a.grammatical_features == b.grammatical_features
+                        && claims_data_eq(&a.claims, &b.claims)
+                })
+                && a.senses.len() == b.senses.len()
+                && a.senses.iter().zip(&b.senses).all(|(a, b)| {
+                    a.id == b.id && a.glosses == b.glosses && claims_data_eq(&a.claims, &b.claims)
+                })
+        }
+
+        let lexemes_eq = match (&self.lexeme, &other.lexeme) {
+            (Some(a), Some(b)) => lexeme_data_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        self.id == other.id
+            && self.entity_type == other.entity_type
+            && self.labels == other.labels
+            && self.descriptions == other.descriptions
+            && self.aliases == other.aliases
+            && self.sitelinks == other.sitelinks
+            && lexemes_eq
+            && claims_data_eq(&self.claims, &other.claims)
+    }
+
+    /// Get the entity as a [GeoJSON](https://geojson.org/) `Feature` with a `Point` geometry,
+    /// using its [coordinate location](Entity::coordinate_location). The feature's properties
+    /// contain the entity's `id` and all of its `labels`, keyed by language.
+    ///
+    /// Returns `None` if the entity has no such coordinate.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    /// # let q513 = wikidata::Entity::from_json(j).unwrap();
+    /// let feature = q513.to_geojson_feature().unwrap();
+    /// assert_eq!(feature["geometry"]["coordinates"][0], 86.925277777778);
+    /// assert_eq!(feature["properties"]["id"], "Q513");
+    /// assert_eq!(feature["properties"]["labels"]["en"], "Mount Everest");
+    /// ```
+    #[must_use]
+    pub fn to_geojson_feature(&self) -> Option<Value> {
+        let (lat, lon) = self.coordinate_location()?;
+        Some(serde_json::json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "Point",
+                // GeoJSON positions are longitude first
+                "coordinates": [lon, lat],
+            },
+            "properties": {
+                "id": self.id.to_string(),
+                "labels": self.labels,
+            },
+        }))
+    }
+}
+
+/// The bounding box of the [coordinate locations](Entity::coordinate_location) of some entities,
+/// as `(south, west, north, east)` in degrees. Entities without a coordinate location are
+/// ignored, and `None` is returned if none of the entities have one.
+///
+/// The box is the smallest one that contains every coordinate. If that box crosses the
+/// antimeridian (180° longitude), `west` is greater than `east`, like in a
+/// [GeoJSON bounding box](https://www.rfc-editor.org/rfc/rfc7946#section-5.2): a set of
+/// entities in Fiji and Samoa gives a box from about 177° to -172°, not one spanning the whole
+/// world.
+///
+/// ## Example
+/// ```
+/// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+/// # let q513 = wikidata::Entity::from_json(j).unwrap();
+/// let (south, west, north, east) = wikidata::bounding_box(&[q513]).unwrap();
+/// assert_eq!((south, west), (north, east));
+/// ```
+#[must_use]
+pub fn bounding_box(entities: &[Entity]) -> Option<(f64, f64, f64, f64)> {
+    let coords: Vec<(f64, f64)> = entities
+        .iter()
+        .filter_map(Entity::coordinate_location)
+        .collect();
+    let south = coords.iter().map(|(lat, _)| *lat).reduce(f64::min)?;
+    let north = coords.iter().map(|(lat, _)| *lat).reduce(f64::max)?;
+    let mut lons: Vec<f64> = coords.iter().map(|(_, lon)| *lon).collect();
+    lons.sort_by(f64::total_cmp);
+    // the box leaves out the biggest gap between neighbouring longitudes, which is the gap across
+    // the antimeridian unless there's a bigger one between two of the coordinates
+    let (mut west, mut east) = (lons[0], lons[lons.len() - 1]);
+    let mut biggest_gap = lons[0] + 360.0 - lons[lons.len() - 1];
+    for pair in lons.windows(2) {
+        if pair[1] - pair[0] > biggest_gap {
+            biggest_gap = pair[1] - pair[0];
+            (west, east) = (pair[1], pair[0]);
+        }
+    }
+    Some((south, west, north, east))
+}
+
+/// An error from parsing an entity in a dump, saying where in the dump the entity is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpError {
+    /// The line number of the entity, starting from 1.
+    pub line: usize,
+    /// The ID of the entity, if the line is valid JSON with a string `id`.
+    pub id: Option<String>,
+    /// The error from parsing the entity.
+    pub source: EntityError,
+}
+
+impl std::fmt::Display for DumpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "line {} ({id}): {}", self.line, self.source),
+            None => write!(f, "line {}: {}", self.line, self.source),
+        }
+    }
+}
+
+impl std::error::Error for DumpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
 
 /// An error related to entity parsing/creation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -589,7 +2425,8 @@ pub enum EntityError {
     MultipleEntities,
     /// The entity had no type
     NoEntityType,
-    /// There are no claims
+    /// There are no claims. `Entity::from_json` no longer returns this; an entity without claims
+    /// is parsed with no claims.
     NoClaims,
     /// The claim ID is missing
     NoClaimId,
@@ -635,9 +2472,261 @@ pub enum EntityError {
     MissingMainsnak,
     /// An hour/minute/second is out of bounds.
     OutOfBoundsTime,
+    /// The time zone offset is a day or more away from UTC
+    InvalidTimezone,
+    /// A sitelink has no title, or an empty title
+    MissingSitelinkTitle,
+    /// The entity ID isn't a string, and couldn't be converted to one
+    NonStringId,
+    /// The input wasn't valid JSON
+    InvalidJson,
+    /// The entity has more claims than allowed by [`ParseOptions::max_claims`]
+    ClaimLimitExceeded,
+    /// A claim's mainsnak has a different property than the one the claim is listed under
+    PropertyMismatch {
+        /// The property the claim is listed under.
+        outer: Pid,
+        /// The property of the mainsnak.
+        inner: Pid,
+    },
+    /// The entity is a redirect to another entity. Redirects have no data of their own, so the
+    /// target entity needs to be fetched instead.
+    Redirect {
+        /// The ID of the redirect itself.
+        from: WikiId,
+        /// The ID of the entity it redirects to.
+        to: WikiId,
+    },
+    /// A lexeme has no language
+    NoLexemeLanguage,
+    /// A lexeme has no lexical category
+    NoLexicalCategory,
+}
+
+impl std::fmt::Display for EntityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityError::FloatParse => write!(f, "a number couldn't be parsed"),
+            EntityError::ExpectedString => write!(f, "expected a string"),
+            EntityError::ExpectedObject => write!(f, "expected an object"),
+            EntityError::ExpectedArray => write!(f, "expected an array"),
+            EntityError::ExpectedNumberString => write!(f, "expected a number or numeric string"),
+            EntityError::ExpectedUriString => write!(f, "expected a URI string"),
+            EntityError::ExpectedQidString => write!(f, "expected a Qid URI string"),
+            EntityError::ExpectedStringDatatype => {
+                write!(f, "expected a string value for a string datatype")
+            }
+            EntityError::TimeEmpty => write!(f, "time string is empty"),
+            EntityError::BadId => write!(f, "invalid ID"),
+            EntityError::NoDateYear => write!(f, "date has no year"),
+            EntityError::NoDateMatched => write!(f, "no date matches the year, month and day"),
+            EntityError::DateAmbiguous => write!(f, "date is ambiguous"),
+            EntityError::InvalidDatatype => write!(f, "invalid datatype"),
+            EntityError::UnknownDatatype => write!(f, "invalid or unknown datatype"),
+            EntityError::MissingHour => write!(f, "time has no hour"),
+            EntityError::MissingMinute => write!(f, "time has no minute"),
+            EntityError::MissingSecond => write!(f, "time has no second"),
+            EntityError::InvalidSnaktype => write!(f, "invalid snaktype"),
+            EntityError::InvalidPrecision => write!(f, "invalid time precision"),
+            EntityError::NoRank => write!(f, "claim has no rank"),
+            EntityError::NumberOutOfBounds => write!(f, "number is out of bounds"),
+            EntityError::NoId => write!(f, "no ID found"),
+            EntityError::NoEntities => write!(f, "no entities in the object"),
+            EntityError::MultipleEntities => write!(f, "multiple entities in the object"),
+            EntityError::NoEntityType => write!(f, "entity has no type"),
+            EntityError::NoClaims => write!(f, "entity has no claims field"),
+            EntityError::NoClaimId => write!(f, "claim has no ID"),
+            EntityError::UnknownRank => write!(f, "unknown rank"),
+            EntityError::NoSnakOrder => write!(f, "reference group has no snaks-order"),
+            EntityError::NoHash => write!(f, "reference group has no hash"),
+            EntityError::NoReferenceSnaks => write!(f, "reference group has no snaks"),
+            EntityError::SnaksOrderIncludesNonSnak => {
+                write!(f, "snaks-order names a property with no snaks")
+            }
+            EntityError::QualifiersOrderButNoObject => {
+                write!(f, "claim has qualifiers-order but no qualifiers")
+            }
+            EntityError::QualiferOrderNamesNonQualifier => {
+                write!(f, "qualifiers-order names a property with no qualifiers")
+            }
+            EntityError::ExpectedKeyvalTextString => {
+                write!(f, "expected a string for a label, description or badge")
+            }
+            EntityError::ExpectedTextValue => {
+                write!(f, "expected a value in a language+value object")
+            }
+            EntityError::ExpectedAliasArray => write!(f, "expected an array of aliases"),
+            EntityError::ExpectedClaimArray => write!(f, "expected an array of claims"),
+            EntityError::ExpectedReferenceArray => write!(f, "expected an array of references"),
+            EntityError::ExpectedReferenceSubsnakArray => {
+                write!(f, "expected an array of reference snaks")
+            }
+            EntityError::ExpectedHashString => write!(f, "expected a hash string"),
+            EntityError::ExpectedLangString => write!(f, "expected a language string"),
+            EntityError::ExpectedAliasString => write!(f, "expected an alias string"),
+            EntityError::ExpectedPidString => write!(f, "expected a Pid string"),
+            EntityError::ExpectedSiteTitleString => write!(f, "expected a sitelink title string"),
+            EntityError::ExpectedSiteBadgesArray => {
+                write!(f, "expected an array of sitelink badges")
+            }
+            EntityError::MissingMainsnak => write!(f, "claim has no mainsnak"),
+            EntityError::OutOfBoundsTime => write!(f, "hour, minute or second is out of bounds"),
+            EntityError::InvalidTimezone => write!(f, "time zone offset is out of bounds"),
+            EntityError::MissingSitelinkTitle => write!(f, "sitelink has no title"),
+            EntityError::NonStringId => write!(f, "entity ID isn't a string"),
+            EntityError::InvalidJson => write!(f, "invalid JSON"),
+            EntityError::ClaimLimitExceeded => write!(f, "entity has too many claims"),
+            EntityError::PropertyMismatch { outer, inner } => {
+                write!(f, "claim listed under {outer} has a mainsnak for {inner}")
+            }
+            EntityError::Redirect { from, to } => write!(f, "{from} is a redirect to {to}"),
+            EntityError::NoLexemeLanguage => write!(f, "lexeme has no language"),
+            EntityError::NoLexicalCategory => write!(f, "lexeme has no lexical category"),
+        }
+    }
+}
+
+impl std::error::Error for EntityError {}
+
+/// Parse a map of language codes to terms, like the `labels` of an entity. Blank terms are left
+/// out.
+pub(crate) fn parse_terms(terms: Option<&Value>) -> Result<BTreeMap<Lang, String>, EntityError> {
+    let Some(terms) = terms else {
+        return Ok(BTreeMap::new());
+    };
+    let terms = terms.as_object().ok_or(EntityError::ExpectedObject)?;
+    let mut map = BTreeMap::new();
+    for (key, val) in terms {
+        // some non-Wikidata Wikibase exports use a plain string instead of a language+value
+        // object
+        let text = match val {
+            Value::String(s) => s,
+            _ => val
+                .as_object()
+                .ok_or(EntityError::ExpectedObject)?
+                .get("value")
+                .ok_or(EntityError::ExpectedLangString)?
+                .as_str()
+                .ok_or(EntityError::ExpectedKeyvalTextString)?,
+        };
+        // blank text is treated as missing
+        if !text.trim().is_empty() {
+            map.insert(Lang(key.clone()), text.to_string());
+        }
+    }
+    Ok(map)
+}
+
+/// Get the first non-blank term in `langs`, falling back to `mul`.
+pub(crate) fn term_with_fallback<'a>(
+    terms: &'a BTreeMap<Lang, String>,
+    langs: &[&str],
+) -> Option<&'a str> {
+    langs
+        .iter()
+        .chain(std::iter::once(&"mul"))
+        .filter_map(|lang| terms.get(&Lang((*lang).to_string())))
+        .map(String::as_str)
+        .find(|term| !term.trim().is_empty())
+}
+
+/// The claims with the highest rank for their property, in the order given. Deprecated claims are
+/// never included.
+pub(crate) fn best_rank_claims<'a, I>(claims: I) -> impl Iterator<Item = &'a (Pid, ClaimValue)>
+where
+    I: Iterator<Item = &'a (Pid, ClaimValue)> + Clone,
+{
+    let mut best_ranks: BTreeMap<Pid, Rank> = BTreeMap::new();
+    for (pid, claim) in claims.clone() {
+        if claim.rank == Rank::Deprecated {
+            continue;
+        }
+        let best = best_ranks.entry(*pid).or_insert(claim.rank);
+        *best = (*best).max(claim.rank);
+    }
+    claims.filter(move |(pid, claim)| best_ranks.get(pid) == Some(&claim.rank))
+}
+
+/// Parse a map of property IDs to lists of claims, like the `claims` of an entity.
+pub(crate) fn parse_claims(
+    claims: Option<&mut Value>,
+) -> Result<Vec<(Pid, ClaimValue)>, EntityError> {
+    let mut parsed = Vec::new();
+    for (pid, claim_list) in claims
+        .map(|claims| claims.as_object_mut().ok_or(EntityError::ExpectedObject))
+        .transpose()?
+        .into_iter()
+        .flatten()
+    {
+        let pid = Pid::from_str(pid).map_err(|_| EntityError::BadId)?;
+        for claim in claim_list
+            .as_array_mut()
+            .ok_or(EntityError::ExpectedClaimArray)?
+        {
+            parsed.push((pid, parse_claim(claim)?));
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_claim(claim: &mut Value) -> Result<ClaimValue, EntityError> {
+    let references = if let Some(ref_groups) = claim.get("references").and_then(Value::as_array) {
+        ref_groups
+            .iter()
+            .map(ReferenceGroup::from_json)
+            .collect::<Result<_, _>>()?
+    } else {
+        Vec::new()
+    };
+    let qualifiers = if let Some(order) = claim.get("qualifiers-order").and_then(Value::as_array) {
+        let qualifiers_json = claim
+            .get("qualifiers")
+            .ok_or(EntityError::QualifiersOrderButNoObject)?
+            .as_object()
+            .ok_or(EntityError::ExpectedObject)?;
+        let mut qualifiers = Vec::new();
+        for pid in order {
+            let pid = pid.as_str().ok_or(EntityError::NoId)?;
+            let pid_id = Pid::from_str(pid).map_err(|_| EntityError::BadId)?;
+            let qual_list = qualifiers_json
+                .get(pid)
+                .and_then(Value::as_array)
+                .ok_or(EntityError::QualiferOrderNamesNonQualifier)?;
+            for qual in qual_list {
+                qualifiers.push((pid_id, ClaimValueData::parse_snak(qual.clone())?));
+            }
+        }
+        qualifiers
+    } else {
+        Vec::new()
+    };
+    let data = ClaimValueData::parse_snak(
+        claim
+            .get_mut("mainsnak")
+            .ok_or(EntityError::MissingMainsnak)?
+            .take(),
+    )?;
+    Ok(ClaimValue {
+        id: claim
+            .get("id")
+            .ok_or(EntityError::NoClaimId)?
+            .as_str()
+            .ok_or(EntityError::NoClaimId)?
+            .to_string(),
+        rank: Rank::from_str(
+            claim
+                .get("rank")
+                .ok_or(EntityError::NoRank)?
+                .as_str()
+                .ok_or(EntityError::NoRank)?,
+        )?,
+        data,
+        qualifiers,
+        references,
+    })
 }
 
-fn get_json_string(json: Value) -> Result<String, EntityError> {
+fn get_json_string(json: &Value) -> Result<String, EntityError> {
     json.as_str()
         .map(ToString::to_string)
         .ok_or(EntityError::ExpectedString)
@@ -653,15 +2742,111 @@ fn parse_wb_number(num: &Value) -> Result<f64, EntityError> {
             } else {
                 &s[..]
             };
-            match s.parse() {
-                Ok(x) => Ok(x),
-                Err(_) => Err(EntityError::FloatParse),
+            // Rust accepts "NaN" and "inf", but they aren't Wikibase numbers (and couldn't be
+            // serialized as JSON numbers)
+            match s.parse::<f64>() {
+                Ok(x) if x.is_finite() => Ok(x),
+                _ => Err(EntityError::FloatParse),
             }
         }
         _ => Err(EntityError::ExpectedNumberString),
     }
 }
 
+/// Encode a page title for use in a URL, the same way as Wikipedia (which leaves some
+/// punctuation unescaped).
+fn encode_title(title: &str) -> String {
+    encode_url_part(&title.replace(' ', "_"))
+}
+
+/// Percent-encode text for use in a URL, leaving the same punctuation unescaped as Wikipedia
+/// does (so slashes in identifiers like DOIs are kept).
+fn encode_url_part(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~'
+            | b';'
+            | b':'
+            | b'@'
+            | b'$'
+            | b'!'
+            | b'*'
+            | b'('
+            | b')'
+            | b','
+            | b'/' => encoded.push(byte as char),
+            _ => write!(encoded, "%{byte:02X}").unwrap(),
+        }
+    }
+    encoded
+}
+
+fn simple_qid_name(qid: Qid) -> String {
+    consts::qid_const_name(qid).map_or_else(
+        || qid.to_string(),
+        |name| name.to_lowercase().replace('_', " "),
+    )
+}
+
+fn simple_pid_name(pid: Pid) -> String {
+    // constants whose names aren't readable once lowercased
+    const NAMES: &[(Pid, &str)] = &[
+        (consts::PSUEDONYM, "pseudonym"),
+        (consts::FB_ID, "facebook id"),
+        (consts::YT_CHANNEL_ID, "youtube channel id"),
+        (consts::IG_USERNAME, "instagram username"),
+        (consts::CEO, "chief executive officer"),
+    ];
+    if let Some((_, name)) = NAMES.iter().find(|(name_pid, _)| *name_pid == pid) {
+        return (*name).to_string();
+    }
+    consts::pid_const_name(pid).map_or_else(
+        || pid.to_string(),
+        |name| name.to_lowercase().replace('_', " "),
+    )
+}
+
+fn entity_iri(qid: Qid) -> String {
+    format!("http://www.wikidata.org/entity/{qid}")
+}
+
+fn format_wb_number(num: f64) -> String {
+    if num.is_sign_negative() {
+        num.to_string()
+    } else {
+        format!("+{num}")
+    }
+}
+
+fn format_wb_time(date_time: &DateTime<Utc>, precision: u8) -> String {
+    use chrono::{Datelike, Timelike};
+    let year = date_time.year();
+    // months and days more precise than the precision are zeroed out, like Wikibase does
+    let month = if precision >= 10 {
+        date_time.month()
+    } else {
+        0
+    };
+    let day = if precision >= 11 { date_time.day() } else { 0 };
+    format!(
+        "{}{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        if year < 0 { '-' } else { '+' },
+        year.unsigned_abs(),
+        month,
+        day,
+        date_time.hour(),
+        date_time.minute(),
+        date_time.second(),
+    )
+}
+
 fn try_get_as_qid(datavalue: &Value) -> Result<Qid, EntityError> {
     match datavalue
         .as_str()
@@ -676,6 +2861,40 @@ fn try_get_as_qid(datavalue: &Value) -> Result<Qid, EntityError> {
     }
 }
 
+/// Parse an IRI that is usually a Wikidata entity, keeping any other IRI as-is.
+fn parse_wb_iri(iri: &Value) -> Result<EntityOrIri, EntityError> {
+    let iri_str = iri.as_str().ok_or(EntityError::ExpectedUriString)?;
+    Ok(match try_get_as_qid(iri) {
+        Ok(qid) => EntityOrIri::Entity(qid),
+        Err(_) => EntityOrIri::Iri(iri_str.to_string()),
+    })
+}
+
+/// Parse the globe of a coordinate. Like Wikibase, a missing globe means Earth.
+fn parse_wb_globe(globe: &Value) -> Result<EntityOrIri, EntityError> {
+    match globe {
+        Value::Null => Ok(consts::EARTH.into()),
+        globe => parse_wb_iri(globe),
+    }
+}
+
+/// Parse the calendar model of a time. Like Wikibase, a missing calendar model means Gregorian.
+fn parse_wb_calendar(calendar: &Value) -> Result<Qid, EntityError> {
+    match calendar {
+        Value::Null => Ok(consts::PROLEPTIC_GREGORIAN_CALENDAR),
+        calendar => try_get_as_qid(calendar),
+    }
+}
+
+/// Parse the unit of a quantity. A missing unit or the unit `"1"` means the quantity has no unit.
+fn parse_wb_unit(unit: &Value) -> Result<Option<EntityOrIri>, EntityError> {
+    match unit {
+        Value::Null => Ok(None),
+        Value::String(s) if s == "1" => Ok(None),
+        unit => parse_wb_iri(unit).map(Some),
+    }
+}
+
 fn take_prop(key: &'static str, claim: &mut Value) -> Value {
     match claim.as_object_mut() {
         Some(obj) => obj.remove(key).unwrap_or(Value::Null),
@@ -683,15 +2902,22 @@ fn take_prop(key: &'static str, claim: &mut Value) -> Value {
     }
 }
 
-fn parse_wb_time(time: &str) -> Result<chrono::DateTime<chrono::offset::Utc>, EntityError> {
+/// Parse a Wikibase time string written in the time zone `offset`, and convert it to UTC.
+fn parse_wb_time(
+    time: &str,
+    offset: FixedOffset,
+) -> Result<chrono::DateTime<chrono::offset::Utc>, EntityError> {
     if time.is_empty() {
         return Err(EntityError::TimeEmpty);
     }
 
     // "Negative years are allowed in formatting but not in parsing.", so we
     // set the era ourselves, after parsing
-    let is_ce = time.chars().next().ok_or(EntityError::TimeEmpty)? == '+';
-    let time = &time[1..];
+    let (is_ce, time) = match (time.strip_prefix('+'), time.strip_prefix('-')) {
+        (Some(time), _) => (true, time),
+        (None, Some(time)) => (false, time),
+        (None, None) => return Err(EntityError::NoDateYear),
+    };
 
     let time_parts: Vec<&str> = time.split('T').collect();
     let dash_parts: Vec<&str> = time_parts[0].split('-').collect();
@@ -724,29 +2950,49 @@ fn parse_wb_time(time: &str) -> Result<chrono::DateTime<chrono::offset::Utc>, En
     };
     let (hour, min, sec) = if time_parts.len() == 2 {
         let colon_parts: Vec<&str> = time_parts[1].split(':').collect();
-        let hour = match colon_parts.get(0).ok_or(EntityError::MissingHour)?.parse() {
-            Ok(x) => x,
-            Err(_) => return Err(EntityError::FloatParse),
+        let Ok(hour) = colon_parts.first().ok_or(EntityError::MissingHour)?.parse() else {
+            return Err(EntityError::FloatParse);
         };
-        let minute = match colon_parts
+        let Ok(minute) = colon_parts
             .get(1)
             .ok_or(EntityError::MissingMinute)?
             .parse()
-        {
-            Ok(x) => x,
-            Err(_) => return Err(EntityError::FloatParse),
+        else {
+            return Err(EntityError::FloatParse);
         };
-        let sec = match colon_parts.get(2).ok_or(EntityError::MissingSecond)?[0..2].parse() {
-            Ok(x) => x,
-            Err(_) => return Err(EntityError::FloatParse),
+        // the trailing Z is sometimes missing, and fractional seconds are truncated
+        let sec_str = colon_parts
+            .get(2)
+            .ok_or(EntityError::MissingSecond)?
+            .trim_end_matches('Z');
+        let sec_str = sec_str.split('.').next().unwrap_or(sec_str);
+        let Ok(sec) = sec_str.parse() else {
+            return Err(EntityError::FloatParse);
         };
         (hour, minute, sec)
     } else {
         (0, 0, 0)
     };
-    Ok(date
+    let date_time = date
         .and_hms_opt(hour, min, sec)
-        .ok_or(EntityError::OutOfBoundsTime)?)
+        .ok_or(EntityError::OutOfBoundsTime)?;
+    // the date and time are the local time at the offset, so undo the offset to get UTC; this can
+    // go past the range Chrono supports
+    date_time
+        .checked_sub_signed(Duration::seconds(i64::from(offset.local_minus_utc())))
+        .ok_or(EntityError::NoDateMatched)
+}
+
+/// Parse the `timezone` field of a time value, an offset from UTC in minutes.
+fn parse_wb_timezone(timezone: &Value) -> Result<FixedOffset, EntityError> {
+    let minutes = match timezone {
+        Value::Null => 0.0,
+        timezone => parse_wb_number(timezone).map_err(|_| EntityError::InvalidTimezone)?,
+    };
+    if minutes.fract() != 0.0 || minutes.abs() >= 1440.0 {
+        return Err(EntityError::InvalidTimezone);
+    }
+    FixedOffset::east_opt(minutes as i32 * 60).ok_or(EntityError::InvalidTimezone)
 }
 
 impl ClaimValueData {
@@ -756,14 +3002,13 @@ impl ClaimValueData {
     /// If the `snak` does not correspond to a valid snak, then an error will be returned.
     pub fn parse_snak(mut snak: Value) -> Result<Self, EntityError> {
         let mut datavalue: Value = take_prop("datavalue", &mut snak);
-        let datatype: &str = &get_json_string(take_prop("datatype", &mut snak))?;
-        let snaktype: &str = &get_json_string(take_prop("snaktype", &mut snak))?;
-        match snaktype {
-            "value" => {}
-            "somevalue" => return Ok(ClaimValueData::UnknownValue),
-            "novalue" => return Ok(ClaimValueData::NoValue),
-            _ => return Err(EntityError::InvalidSnaktype),
-        };
+        match Snaktype::from_str(&get_json_string(&take_prop("snaktype", &mut snak))?)? {
+            Snaktype::Value => {}
+            Snaktype::SomeValue => return Ok(ClaimValueData::UnknownValue),
+            Snaktype::NoValue => return Ok(ClaimValueData::NoValue),
+        }
+        // only needed (and only required) for snaks with a value
+        let datatype: &str = &get_json_string(&take_prop("datatype", &mut snak))?;
         let type_str = take_prop("type", &mut datavalue)
             .as_str()
             .ok_or(EntityError::InvalidSnaktype)?
@@ -787,125 +3032,635 @@ impl ClaimValueData {
                     _ => Err(EntityError::InvalidDatatype),
                 }
             }
-            "wikibase-entityid" => {
-                // the ID could be a entity, lexeme, property, form, or sense
-                let id = get_json_string(take_prop("id", &mut value))?;
-                match id.chars().next().ok_or(EntityError::BadId)? {
-                    'Q' => Ok(ClaimValueData::Item(Qid(id[1..]
-                        .parse()
-                        .map_err(|_| EntityError::BadId)?))),
-                    'P' => Ok(ClaimValueData::Property(Pid(id[1..]
-                        .parse()
-                        .map_err(|_| EntityError::BadId)?))),
-                    'L' => {
-                        // sense: "L1-S2", form: "L1-F2", lexeme: "L2"
-                        let parts: Vec<&str> = id.split('-').collect();
-                        match parts.len() {
-                            1 => Ok(ClaimValueData::Lexeme(Lid(id[1..]
-                                .parse()
-                                .map_err(|_| EntityError::BadId)?))),
-                            2 => match parts[1].chars().next().ok_or(EntityError::BadId)? {
-                                'F' => Ok(ClaimValueData::Form(Fid(
-                                    Lid(parts[0][1..].parse().map_err(|_| EntityError::BadId)?),
-                                    parts[1][1..].parse().map_err(|_| EntityError::BadId)?,
-                                ))),
-                                'S' => Ok(ClaimValueData::Sense(Sid(
-                                    Lid(parts[0][1..].parse().map_err(|_| EntityError::BadId)?),
-                                    parts[1][1..].parse().map_err(|_| EntityError::BadId)?,
-                                ))),
-                                _ => Err(EntityError::BadId),
-                            },
-                            _ => Err(EntityError::BadId),
-                        }
-                    }
-                    _ => Err(EntityError::BadId),
-                }
+            "wikibase-entityid" => {
+                // the ID could be a entity, lexeme, property, form, or sense
+                let id = match take_prop("id", &mut value) {
+                    // older dumps only have the entity type and numeric ID
+                    Value::Null => {
+                        let prefix = match take_prop("entity-type", &mut value).as_str() {
+                            Some("item") => 'Q',
+                            Some("property") => 'P',
+                            Some("lexeme") => 'L',
+                            _ => return Err(EntityError::BadId),
+                        };
+                        let num = take_prop("numeric-id", &mut value)
+                            .as_u64()
+                            .ok_or(EntityError::BadId)?;
+                        format!("{prefix}{num}")
+                    }
+                    id => get_json_string(&id)?,
+                };
+                match id.chars().next().ok_or(EntityError::BadId)? {
+                    'Q' => Ok(ClaimValueData::Item(Qid(id[1..]
+                        .parse()
+                        .map_err(|_| EntityError::BadId)?))),
+                    'P' => Ok(ClaimValueData::Property(Pid(id[1..]
+                        .parse()
+                        .map_err(|_| EntityError::BadId)?))),
+                    'L' => {
+                        // sense: "L1-S2", form: "L1-F2", lexeme: "L2"
+                        let parts: Vec<&str> = id.split('-').collect();
+                        match parts.len() {
+                            1 => Ok(ClaimValueData::Lexeme(Lid(id[1..]
+                                .parse()
+                                .map_err(|_| EntityError::BadId)?))),
+                            2 => match parts[1].chars().next().ok_or(EntityError::BadId)? {
+                                'F' => Ok(ClaimValueData::Form(Fid(
+                                    Lid(parts[0][1..].parse().map_err(|_| EntityError::BadId)?),
+                                    parts[1][1..].parse().map_err(|_| EntityError::BadId)?,
+                                ))),
+                                'S' => Ok(ClaimValueData::Sense(Sid(
+                                    Lid(parts[0][1..].parse().map_err(|_| EntityError::BadId)?),
+                                    parts[1][1..].parse().map_err(|_| EntityError::BadId)?,
+                                ))),
+                                _ => Err(EntityError::BadId),
+                            },
+                            _ => Err(EntityError::BadId),
+                        }
+                    }
+                    _ => Err(EntityError::BadId),
+                }
+            }
+            "globecoordinate" => {
+                Ok(ClaimValueData::GlobeCoordinate {
+                    // altitude field is deprecated and we ignore it
+                    lat: parse_wb_number(&take_prop("latitude", &mut value))?,
+                    lon: parse_wb_number(&take_prop("longitude", &mut value))?,
+                    // sometimes precision is missing
+                    precision: parse_wb_number(&take_prop("precision", &mut value)).ok(),
+                    globe: parse_wb_globe(&take_prop("globe", &mut value))?,
+                })
+            }
+            "quantity" => Ok(ClaimValueData::Quantity {
+                amount: parse_wb_number(&take_prop("amount", &mut value))?,
+                upper_bound: parse_wb_number(&take_prop("upperBound", &mut value)).ok(),
+                lower_bound: parse_wb_number(&take_prop("lowerBound", &mut value)).ok(),
+                unit: parse_wb_unit(&take_prop("unit", &mut value))?,
+            }),
+            "time" => {
+                let time = get_json_string(&take_prop("time", &mut value))?;
+                let precision = parse_wb_number(&take_prop("precision", &mut value))
+                    .map_err(|_| EntityError::InvalidPrecision)?
+                    as u8;
+                let calendar = parse_wb_calendar(&take_prop("calendarmodel", &mut value))?;
+                // dates without a time of day aren't shifted by the offset, since that would move
+                // them to a different day
+                let timezone = parse_wb_timezone(&take_prop("timezone", &mut value))?;
+                let timezone = if precision > 11 { timezone } else { Utc.fix() };
+                // Chrono can't handle a few edge cases (really old years), so those are kept
+                // as-is, and times that can't be parsed at all are treated as unknown
+                Ok(match parse_wb_time(&time, timezone) {
+                    Ok(date_time) => ClaimValueData::DateTime {
+                        date_time,
+                        precision,
+                        calendar,
+                    },
+                    Err(_) => match WbTime::parse(&time, precision) {
+                        Ok(time) => ClaimValueData::OutOfRangeTime { time, calendar },
+                        Err(_) => ClaimValueData::UnknownValue,
+                    },
+                })
+            }
+            "monolingualtext" => Ok(ClaimValueData::MonolingualText(Text {
+                text: get_json_string(&take_prop("text", &mut value))?,
+                lang: Lang(get_json_string(&take_prop("language", &mut value))?),
+            })),
+            _ => Err(EntityError::UnknownDatatype),
+        }
+    }
+
+    /// Convert the data to a single snak in the Wikibase JSON representation, the reverse of
+    /// [`ClaimValueData::parse_snak`]. The `datatype` is needed because several datatypes (like
+    /// `string` and `url`) share the same value representation; it's used for the snak's
+    /// `datatype` field as-is.
+    ///
+    /// The snak doesn't have a `property` field, since the data doesn't know which property it's
+    /// for, so add one before sending the snak to the Wikibase API.
+    /// [`MultilingualText`](ClaimValueData::MultilingualText) has no Wikibase representation, so
+    /// it is converted to `null`; [`Entity::to_wikibase_json`] leaves such snaks out.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{ClaimValueData, Datatype, Qid};
+    /// let snak = ClaimValueData::Item(Qid(5)).to_snak_json(Datatype::Item);
+    /// assert_eq!(snak["datavalue"]["value"]["id"], "Q5");
+    /// assert_eq!(ClaimValueData::parse_snak(snak), Ok(ClaimValueData::Item(Qid(5))));
+    /// ```
+    #[must_use]
+    pub fn to_snak_json(&self, datatype: Datatype) -> Value {
+        let (value, value_type) = match self {
+            ClaimValueData::NoValue => {
+                return serde_json::json!({
+                    "snaktype": "novalue",
+                    "datatype": datatype.as_str(),
+                })
+            }
+            ClaimValueData::UnknownValue => {
+                return serde_json::json!({
+                    "snaktype": "somevalue",
+                    "datatype": datatype.as_str(),
+                })
+            }
+            ClaimValueData::MultilingualText(_) => return Value::Null,
+            ClaimValueData::CommonsMedia(s)
+            | ClaimValueData::String(s)
+            | ClaimValueData::ExternalID(s)
+            | ClaimValueData::Url(s)
+            | ClaimValueData::MathExpr(s)
+            | ClaimValueData::GeoShape(s)
+            | ClaimValueData::MusicNotation(s)
+            | ClaimValueData::TabularData(s) => (Value::String(s.clone()), "string"),
+            ClaimValueData::Item(qid) => (
+                serde_json::json!({
+                    "entity-type": "item",
+                    "numeric-id": qid.0,
+                    "id": qid.to_string(),
+                }),
+                "wikibase-entityid",
+            ),
+            ClaimValueData::Property(pid) => (
+                serde_json::json!({
+                    "entity-type": "property",
+                    "numeric-id": pid.0,
+                    "id": pid.to_string(),
+                }),
+                "wikibase-entityid",
+            ),
+            ClaimValueData::Lexeme(lid) => (
+                serde_json::json!({
+                    "entity-type": "lexeme",
+                    "numeric-id": lid.0,
+                    "id": lid.to_string(),
+                }),
+                "wikibase-entityid",
+            ),
+            ClaimValueData::Form(fid) => (
+                serde_json::json!({
+                    "entity-type": "form",
+                    "id": fid.to_string(),
+                }),
+                "wikibase-entityid",
+            ),
+            ClaimValueData::Sense(sid) => (
+                serde_json::json!({
+                    "entity-type": "sense",
+                    "id": sid.to_string(),
+                }),
+                "wikibase-entityid",
+            ),
+            ClaimValueData::GlobeCoordinate {
+                lat,
+                lon,
+                precision,
+                globe,
+            } => (
+                serde_json::json!({
+                    "latitude": lat,
+                    "longitude": lon,
+                    "altitude": null,
+                    "precision": precision,
+                    "globe": globe.to_iri(),
+                }),
+                "globecoordinate",
+            ),
+            ClaimValueData::Quantity {
+                amount,
+                lower_bound,
+                upper_bound,
+                unit,
+            } => {
+                let mut value = serde_json::json!({
+                    "amount": format_wb_number(*amount),
+                    "unit": unit.as_ref().map_or_else(|| "1".to_string(), EntityOrIri::to_iri),
+                });
+                if let Some(upper_bound) = upper_bound {
+                    value["upperBound"] = format_wb_number(*upper_bound).into();
+                }
+                if let Some(lower_bound) = lower_bound {
+                    value["lowerBound"] = format_wb_number(*lower_bound).into();
+                }
+                (value, "quantity")
+            }
+            ClaimValueData::DateTime {
+                date_time,
+                precision,
+                calendar,
+            } => (
+                serde_json::json!({
+                    "time": format_wb_time(date_time, *precision),
+                    "timezone": 0,
+                    "before": 0,
+                    "after": 0,
+                    "precision": precision,
+                    "calendarmodel": entity_iri(*calendar),
+                }),
+                "time",
+            ),
+            ClaimValueData::OutOfRangeTime { time, calendar } => (
+                serde_json::json!({
+                    "time": time.to_string(),
+                    "timezone": 0,
+                    "before": 0,
+                    "after": 0,
+                    "precision": time.precision,
+                    "calendarmodel": entity_iri(*calendar),
+                }),
+                "time",
+            ),
+            ClaimValueData::MonolingualText(text) => (
+                serde_json::json!({
+                    "text": text.text,
+                    "language": text.lang.0,
+                }),
+                "monolingualtext",
+            ),
+        };
+        serde_json::json!({
+            "snaktype": "value",
+            "datavalue": {
+                "value": value,
+                "type": value_type,
+            },
+            "datatype": datatype.as_str(),
+        })
+    }
+
+    /// The datatype of the data, if it can be told from the data alone. This is `None` for
+    /// [`NoValue`](ClaimValueData::NoValue), [`UnknownValue`](ClaimValueData::UnknownValue),
+    /// and [`MultilingualText`](ClaimValueData::MultilingualText).
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{ClaimValueData, Datatype};
+    /// let data = ClaimValueData::Url("https://example.org/".to_string());
+    /// assert_eq!(data.datatype(), Some(Datatype::Url));
+    /// assert_eq!(ClaimValueData::NoValue.datatype(), None);
+    /// ```
+    #[must_use]
+    pub fn datatype(&self) -> Option<Datatype> {
+        Some(match self {
+            ClaimValueData::CommonsMedia(_) => Datatype::CommonsMedia,
+            ClaimValueData::GlobeCoordinate { .. } => Datatype::GlobeCoordinate,
+            ClaimValueData::Item(_) => Datatype::Item,
+            ClaimValueData::Property(_) => Datatype::Property,
+            ClaimValueData::String(_) => Datatype::String,
+            ClaimValueData::MonolingualText(_) => Datatype::MonolingualText,
+            ClaimValueData::ExternalID(_) => Datatype::ExternalID,
+            ClaimValueData::Quantity { .. } => Datatype::Quantity,
+            ClaimValueData::DateTime { .. } | ClaimValueData::OutOfRangeTime { .. } => {
+                Datatype::Time
+            }
+            ClaimValueData::Url(_) => Datatype::Url,
+            ClaimValueData::MathExpr(_) => Datatype::MathExpr,
+            ClaimValueData::GeoShape(_) => Datatype::GeoShape,
+            ClaimValueData::MusicNotation(_) => Datatype::MusicNotation,
+            ClaimValueData::TabularData(_) => Datatype::TabularData,
+            ClaimValueData::Lexeme(_) => Datatype::Lexeme,
+            ClaimValueData::Form(_) => Datatype::Form,
+            ClaimValueData::Sense(_) => Datatype::Sense,
+            ClaimValueData::MultilingualText(_)
+            | ClaimValueData::NoValue
+            | ClaimValueData::UnknownValue => return None,
+        })
+    }
+
+    /// The snaktype of a snak with this data.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{ClaimValueData, Qid, Snaktype};
+    /// assert_eq!(ClaimValueData::Item(Qid(5)).snaktype(), Snaktype::Value);
+    /// assert_eq!(ClaimValueData::UnknownValue.snaktype(), Snaktype::SomeValue);
+    /// assert_eq!(ClaimValueData::NoValue.snaktype(), Snaktype::NoValue);
+    /// ```
+    #[must_use]
+    pub fn snaktype(&self) -> Snaktype {
+        match self {
+            ClaimValueData::UnknownValue => Snaktype::SomeValue,
+            ClaimValueData::NoValue => Snaktype::NoValue,
+            _ => Snaktype::Value,
+        }
+    }
+
+    /// If the data is a point in time, get it as a [`WbTime`]. This works for both
+    /// [`DateTime`](ClaimValueData::DateTime) and
+    /// [`OutOfRangeTime`](ClaimValueData::OutOfRangeTime), so it's the easiest way to handle
+    /// times that may be out of Chrono's range.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q1.json")).unwrap();
+    /// # let universe = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::Pid;
+    /// let start_time = universe.pid_claims(Pid(580)).next().unwrap();
+    /// assert_eq!(start_time.data.wb_time().unwrap().year, -13_798_000_000);
+    /// ```
+    #[must_use]
+    pub fn wb_time(&self) -> Option<WbTime> {
+        match self {
+            ClaimValueData::DateTime {
+                date_time,
+                precision,
+                ..
+            } => Some(WbTime::from_chrono(date_time, *precision)),
+            ClaimValueData::OutOfRangeTime { time, .. } => Some(*time),
+            _ => None,
+        }
+    }
+
+    /// If the data is a [`DateTime`](ClaimValueData::DateTime), get it as a Chrono `DateTime`.
+    /// Use [`ClaimValueData::wb_time`] to also get times that are out of Chrono's range.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use chrono::Datelike;
+    /// use wikidata::consts;
+    /// let birth = q42.pid_claims(consts::DATE_OF_BIRTH).next().unwrap();
+    /// assert_eq!(birth.data.as_datetime().unwrap().year(), 1952);
+    /// ```
+    #[must_use]
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            ClaimValueData::DateTime { date_time, .. } => Some(*date_time),
+            _ => None,
+        }
+    }
+
+    /// Render the data as a plain string, as described in [`Entity::to_flat_record`]. Returns
+    /// `None` for [`NoValue`](ClaimValueData::NoValue) and
+    /// [`UnknownValue`](ClaimValueData::UnknownValue).
+    pub(crate) fn to_display_string(&self) -> Option<String> {
+        Some(match self {
+            ClaimValueData::CommonsMedia(s)
+            | ClaimValueData::String(s)
+            | ClaimValueData::ExternalID(s)
+            | ClaimValueData::Url(s)
+            | ClaimValueData::MathExpr(s)
+            | ClaimValueData::GeoShape(s)
+            | ClaimValueData::MusicNotation(s)
+            | ClaimValueData::TabularData(s) => s.clone(),
+            ClaimValueData::Item(id) => id.to_string(),
+            ClaimValueData::Property(id) => id.to_string(),
+            ClaimValueData::Lexeme(id) => id.to_string(),
+            ClaimValueData::Form(id) => id.to_string(),
+            ClaimValueData::Sense(id) => id.to_string(),
+            ClaimValueData::MonolingualText(text) => text.text.clone(),
+            ClaimValueData::MultilingualText(texts) => texts
+                .iter()
+                .map(|text| &text.text[..])
+                .collect::<Vec<_>>()
+                .join("; "),
+            ClaimValueData::GlobeCoordinate { lat, lon, .. } => format!("{lat},{lon}"),
+            ClaimValueData::Quantity { amount, unit, .. } => match unit {
+                Some(unit) => match unit.as_qid().and_then(Qid::unit_suffix) {
+                    Some(suffix) => format!("{amount}{suffix}"),
+                    None => format!("{amount} {unit}"),
+                },
+                None => amount.to_string(),
+            },
+            ClaimValueData::DateTime {
+                date_time,
+                precision,
+                ..
+            } => match precision {
+                0..=9 => date_time.format("%Y").to_string(),
+                10 => date_time.format("%Y-%m").to_string(),
+                _ => date_time.format("%Y-%m-%d").to_string(),
+            },
+            ClaimValueData::OutOfRangeTime { time, .. } => match (time.month, time.day) {
+                (Some(month), Some(day)) => format!("{}-{month:02}-{day:02}", time.year),
+                (Some(month), None) => format!("{}-{month:02}", time.year),
+                _ => time.year.to_string(),
+            },
+            ClaimValueData::NoValue | ClaimValueData::UnknownValue => return None,
+        })
+    }
+
+    /// If this is a [`Quantity`](ClaimValueData::Quantity), format it as a human-readable
+    /// string, like `1.96 m`. The unit's [suffix](Qid::unit_suffix) is appended if there is one;
+    /// otherwise only the amount is shown. If the bounds are the same distance from the amount,
+    /// that distance is shown too, like `1.96±0.01 m`.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{consts, ClaimValueData};
+    /// let height = ClaimValueData::Quantity {
+    ///     amount: 1.96,
+    ///     lower_bound: Some(1.95),
+    ///     upper_bound: Some(1.97),
+    ///     unit: Some(consts::METRE.into()),
+    /// };
+    /// assert_eq!(height.format_quantity().unwrap(), "1.96±0.01 m");
+    /// ```
+    #[must_use]
+    pub fn format_quantity(&self) -> Option<String> {
+        let ClaimValueData::Quantity {
+            amount,
+            lower_bound,
+            upper_bound,
+            unit,
+        } = self
+        else {
+            return None;
+        };
+        let suffix = unit
+            .as_ref()
+            .and_then(EntityOrIri::as_qid)
+            .and_then(Qid::unit_suffix)
+            .unwrap_or_default();
+        let bound = match (lower_bound, upper_bound) {
+            (Some(lower), Some(upper)) => {
+                // format the distance with the most decimal places of any of the numbers, so that
+                // float errors don't show up
+                let decimals = [*amount, *lower, *upper]
+                    .iter()
+                    .map(|x| x.to_string().split('.').nth(1).map_or(0, str::len))
+                    .max()
+                    .unwrap_or_default();
+                let below = format!("{:.*}", decimals, amount - lower);
+                let above = format!("{:.*}", decimals, upper - amount);
+                (below == above && (upper - amount) != 0.0).then_some(above)
+            }
+            _ => None,
+        };
+        Some(match bound {
+            Some(bound) => format!("{amount}±{bound}{suffix}"),
+            None => format!("{amount}{suffix}"),
+        })
+    }
+
+    /// If this is a [`Quantity`](ClaimValueData::Quantity) with a commonly used unit, get the
+    /// amount converted to the corresponding SI unit, along with the symbol of that unit. Units
+    /// that are measured in a combination of SI base units (like `m²` or `kg/m³`) are converted
+    /// too.
+    ///
+    /// Returns `None` if this isn't a quantity, the quantity has no unit, or the unit can't be
+    /// converted by multiplication alone (like degrees Celsius).
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{consts, ClaimValueData};
+    /// let weight = ClaimValueData::Quantity {
+    ///     amount: 1500.0,
+    ///     lower_bound: None,
+    ///     upper_bound: None,
+    ///     unit: Some(consts::GRAM.into()),
+    /// };
+    /// assert_eq!(weight.quantity_si(), Some((1.5, "kg")));
+    /// ```
+    #[must_use]
+    pub fn quantity_si(&self) -> Option<(f64, &'static str)> {
+        match self {
+            ClaimValueData::Quantity {
+                amount,
+                unit: Some(unit),
+                ..
+            } => {
+                let (factor, symbol) = consts::si_conversion(unit.as_qid()?)?;
+                Some((amount * factor, symbol))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Compare two pieces of claim data chronologically, for sorting them with `sort_by`. Both
+/// [`DateTime`](ClaimValueData::DateTime)s and [`OutOfRangeTime`](ClaimValueData::OutOfRangeTime)s
+/// are compared by their [`WbTime`], and any other data sorts after all of the times.
+///
+/// ## Example
+/// Sorting Portugal's population statements by their point in time:
+/// ```
+/// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+/// # let portugal = wikidata::Entity::from_json(j).unwrap();
+/// use wikidata::{cmp_datetime, consts};
+/// let mut populations: Vec<_> = portugal.pid_claims(consts::POPULATION).collect();
+/// populations.sort_by(|a, b| {
+///     let point_in_time = |claim: &wikidata::ClaimValue| {
+///         claim.first_qualifier(consts::POINT_IN_TIME).cloned().unwrap_or_default()
+///     };
+///     cmp_datetime(&point_in_time(a), &point_in_time(b))
+/// });
+/// let years: Vec<i64> = populations
+///     .iter()
+///     .filter_map(|claim| claim.first_qualifier(consts::POINT_IN_TIME)?.wb_time())
+///     .map(|time| time.year)
+///     .collect();
+/// assert!(years.windows(2).all(|pair| pair[0] <= pair[1]));
+/// ```
+#[must_use]
+pub fn cmp_datetime(a: &ClaimValueData, b: &ClaimValueData) -> Ordering {
+    match (a.wb_time(), b.wb_time()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+// see the docs on `ClaimValueData` for the caveat with `NaN`s
+impl Eq for ClaimValueData {}
+
+impl Hash for ClaimValueData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // -0.0 == 0.0, so they need to hash the same
+        fn float_bits(x: f64) -> u64 {
+            if x == 0.0 {
+                0
+            } else {
+                x.to_bits()
+            }
+        }
+
+        mem::discriminant(self).hash(state);
+        match self {
+            ClaimValueData::GlobeCoordinate {
+                lat,
+                lon,
+                precision,
+                globe,
+            } => {
+                float_bits(*lat).hash(state);
+                float_bits(*lon).hash(state);
+                precision.map(float_bits).hash(state);
+                globe.hash(state);
             }
-            "globecoordinate" => {
-                Ok(ClaimValueData::GlobeCoordinate {
-                    // altitude field is deprecated and we ignore it
-                    lat: parse_wb_number(&take_prop("latitude", &mut value))?,
-                    lon: parse_wb_number(&take_prop("longitude", &mut value))?,
-                    // sometimes precision is missing, default it to 1.0
-                    precision: parse_wb_number(&take_prop("precision", &mut value)).unwrap_or(1.0),
-                    // globe *can* be any IRI, but it practice it's almost always an entity URI
-                    // so we return None if it doesn't match our expectations
-                    globe: try_get_as_qid(&take_prop("globe", &mut value))?,
-                })
+            ClaimValueData::Quantity {
+                amount,
+                lower_bound,
+                upper_bound,
+                unit,
+            } => {
+                float_bits(*amount).hash(state);
+                lower_bound.map(float_bits).hash(state);
+                upper_bound.map(float_bits).hash(state);
+                unit.hash(state);
             }
-            "quantity" => Ok(ClaimValueData::Quantity {
-                amount: parse_wb_number(&take_prop("amount", &mut value))?,
-                upper_bound: parse_wb_number(&take_prop("upperBound", &mut value)).ok(),
-                lower_bound: parse_wb_number(&take_prop("lowerBound", &mut value)).ok(),
-                unit: try_get_as_qid(&take_prop("unit", &mut value)).ok(),
-            }),
-            // our time parsing code can't handle a few edge cases (really old years), so we
-            "time" => Ok(
-                match parse_wb_time(&get_json_string(take_prop("time", &mut value))?) {
-                    Ok(date_time) => ClaimValueData::DateTime {
-                        date_time,
-                        precision: parse_wb_number(&take_prop("precision", &mut value))
-                            .map_err(|_| EntityError::InvalidPrecision)?
-                            as u8,
-                    },
-                    Err(_) => ClaimValueData::UnknownValue,
-                },
-            ),
-            "monolingualtext" => Ok(ClaimValueData::MonolingualText(Text {
-                text: get_json_string(take_prop("text", &mut value))?,
-                lang: Lang(get_json_string(take_prop("language", &mut value))?),
-            })),
-            _ => Err(EntityError::UnknownDatatype),
+            ClaimValueData::CommonsMedia(string)
+            | ClaimValueData::String(string)
+            | ClaimValueData::ExternalID(string)
+            | ClaimValueData::Url(string)
+            | ClaimValueData::MathExpr(string)
+            | ClaimValueData::GeoShape(string)
+            | ClaimValueData::MusicNotation(string)
+            | ClaimValueData::TabularData(string) => string.hash(state),
+            ClaimValueData::Item(qid) => qid.hash(state),
+            ClaimValueData::Property(pid) => pid.hash(state),
+            ClaimValueData::MonolingualText(text) => text.hash(state),
+            ClaimValueData::MultilingualText(texts) => texts.hash(state),
+            ClaimValueData::DateTime {
+                date_time,
+                precision,
+                calendar,
+            } => {
+                date_time.hash(state);
+                precision.hash(state);
+                calendar.hash(state);
+            }
+            ClaimValueData::OutOfRangeTime { time, calendar } => {
+                time.hash(state);
+                calendar.hash(state);
+            }
+            ClaimValueData::Lexeme(lid) => lid.hash(state),
+            ClaimValueData::Form(fid) => fid.hash(state),
+            ClaimValueData::Sense(sid) => sid.hash(state),
+            ClaimValueData::NoValue | ClaimValueData::UnknownValue => {}
         }
     }
 }
 
 impl ClaimValue {
-    /// Try to parse a JSON claim to a claim value.
+    /// Try to parse a JSON claim to a claim value. If `skip_id` is true, the claim ID is left
+    /// empty. If `skip_deprecated` is true, deprecated claims return `None`; otherwise they're
+    /// kept with [`Rank::Deprecated`], like [`Entity::from_json`] does.
     #[must_use]
-    pub fn get_prop_from_snak(mut claim: Value, skip_id: bool) -> Option<ClaimValue> {
-        let rank = match take_prop("rank", &mut claim).as_str()? {
-            "deprecated" => {
-                return None;
-            }
-            "normal" => Rank::Normal,
-            "preferred" => Rank::Preferred,
-            _ => return None,
-        };
+    pub fn get_prop_from_snak(
+        mut claim: Value,
+        skip_id: bool,
+        skip_deprecated: bool,
+    ) -> Option<ClaimValue> {
+        let rank = Rank::from_str(take_prop("rank", &mut claim).as_str()?).ok()?;
+        if skip_deprecated && rank == Rank::Deprecated {
+            return None;
+        }
         let mainsnak = take_prop("mainsnak", &mut claim);
         let data = ClaimValueData::parse_snak(mainsnak).ok()?;
         let references = if let Some(arr) = take_prop("references", &mut claim).as_array() {
-            let mut v: Vec<ReferenceGroup> = Vec::with_capacity(arr.len());
-            for reference_group in arr {
-                let reference_group = reference_group.as_object()?;
-                let mut claims = Vec::with_capacity(reference_group["snaks"].as_array()?.len());
-                let snaks = reference_group["snaks"].as_object()?;
-                for (pid, snak_group) in snaks.iter() {
-                    for snak in snak_group.as_array()?.iter() {
-                        // clone, meh
-                        let owned_snak = snak.clone().take();
-                        if let Ok(x) = ClaimValueData::parse_snak(owned_snak) {
-                            claims.push((Pid(pid[1..].parse().ok()?), x));
-                        }
-                    }
-                }
-                v.push(ReferenceGroup {
-                    claims,
-                    hash: reference_group.get("hash")?.as_str()?.to_string(),
-                });
-            }
-            v
+            arr.iter()
+                .map(ReferenceGroup::from_json)
+                .collect::<Result<_, _>>()
+                .ok()?
         } else {
             Vec::new()
         };
         let qualifiers_json = take_prop("qualifiers", &mut claim);
         let qualifiers = if qualifiers_json.is_object() {
             let mut v: Vec<(Pid, ClaimValueData)> = vec![];
-            for (pid, claim_array_json) in qualifiers_json.as_object()?.iter() {
+            for (pid, claim_array_json) in qualifiers_json.as_object()? {
                 // yep it's a clone, meh
-                let mut claim_array = if let Value::Array(x) = claim_array_json.clone().take() {
-                    x
-                } else {
+                let Value::Array(mut claim_array) = claim_array_json.clone().take() else {
                     return None;
                 };
                 for claim in claim_array.drain(..) {
@@ -931,7 +3686,6 @@ impl ClaimValue {
         })
     }
 
-    #[must_use]
     /// Returns an iterator of references to all the qualifer claim data for a property ID.
     ///
     /// ## Example
@@ -948,10 +3702,184 @@ impl ClaimValue {
             .filter(move |(claim_pid, _)| *claim_pid == pid)
             .map(|(_, value)| value)
     }
+
+    /// Get the first qualifier for a property ID, in the order of [`ClaimValue::qualifiers`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+    /// # let portugal = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, ClaimValueData};
+    /// for population in portugal.pid_claims(consts::POPULATION) {
+    ///     let time = population.first_qualifier(consts::POINT_IN_TIME);
+    ///     assert!(matches!(time, Some(ClaimValueData::DateTime { .. })));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn first_qualifier(&self, pid: Pid) -> Option<&ClaimValueData> {
+        self.qualifier_pid_claims(pid).next()
+    }
+
+    /// Get the latitude and longitude of the first qualifier for a property ID that is a
+    /// [`GlobeCoordinate`](ClaimValueData::GlobeCoordinate), such as a
+    /// [coordinate location](consts::COORDINATE_LOCATION) qualifier giving where a statement
+    /// applies.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{consts, ClaimValue, ClaimValueData, Qid};
+    /// let claim = ClaimValue {
+    ///     qualifiers: vec![(
+    ///         consts::COORDINATE_LOCATION,
+    ///         ClaimValueData::GlobeCoordinate {
+    ///             lat: 50.9,
+    ///             lon: 0.5,
+    ///             precision: Some(0.1),
+    ///             globe: consts::EARTH.into(),
+    ///         },
+    ///     )],
+    ///     ..ClaimValue::default()
+    /// };
+    /// assert_eq!(claim.qualifier_coordinate(consts::COORDINATE_LOCATION), Some((50.9, 0.5)));
+    /// ```
+    #[must_use]
+    pub fn qualifier_coordinate(&self, pid: Pid) -> Option<(f64, f64)> {
+        self.qualifier_pid_claims(pid).find_map(|data| match data {
+            ClaimValueData::GlobeCoordinate { lat, lon, .. } => Some((*lat, *lon)),
+            _ => None,
+        })
+    }
+
+    /// The snaktype of the claim's main snak, which follows from its [data](ClaimValue::data).
+    /// For qualifiers and references, use [`ClaimValueData::snaktype`].
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{ClaimValue, ClaimValueData, Snaktype};
+    /// let claim = ClaimValue::default();
+    /// assert_eq!(claim.data, ClaimValueData::NoValue);
+    /// assert_eq!(claim.snaktype(), Snaktype::NoValue);
+    /// ```
+    #[must_use]
+    pub fn snaktype(&self) -> Snaktype {
+        self.data.snaktype()
+    }
+
+    /// The ID of the entity the claim belongs to, from the start of the claim's
+    /// [ID](ClaimValue::id) (the part before the `$`). Some older claim IDs start with a
+    /// lowercase letter, like `q42$...`, so the case of the entity ID is ignored. Returns `None`
+    /// if the claim ID doesn't start with an entity ID, such as claims made in code.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{ClaimValue, Fid, Lid, Qid, WikiId};
+    /// let (_, claim) = q42.claim_by_id("q42$881F40DC-0AFE-4FEB-B882-79600D234273").unwrap();
+    /// assert_eq!(claim.entity_id(), Some(WikiId::EntityId(Qid(42))));
+    ///
+    /// let form_claim = ClaimValue {
+    ///     id: "L5-F9$6C2A4A6F-3B6D-4F1A-9A7E-5C3B0B8E2D11".to_string(),
+    ///     ..ClaimValue::default()
+    /// };
+    /// assert_eq!(form_claim.entity_id(), Some(WikiId::FormId(Fid(Lid(5), 9))));
+    /// assert_eq!(ClaimValue::default().entity_id(), None);
+    /// ```
+    #[must_use]
+    pub fn entity_id(&self) -> Option<WikiId> {
+        let (entity_id, _) = self.id.split_once('$')?;
+        WikiId::from_str(&entity_id.to_ascii_uppercase()).ok()
+    }
+
+    /// Whether the claim has at least one reference group with a claim in it.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// assert!(q42.best_statement(wikidata::consts::DATE_OF_BIRTH).unwrap().is_referenced());
+    /// ```
+    #[must_use]
+    pub fn is_referenced(&self) -> bool {
+        self.references
+            .iter()
+            .any(|reference| !reference.claims.is_empty())
+    }
+
+    /// Get the time of the first qualifier for a property ID that is a
+    /// [`DateTime`](ClaimValueData::DateTime), such as a
+    /// [point in time](consts::POINT_IN_TIME) qualifier giving when a statement was true.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// let (_, spouse) = q42.claim_by_id("q42$b88670f8-456b-3ecb-cf3d-2bca2cf7371e").unwrap();
+    /// let start = spouse.qualifier_time(wikidata::Pid(580)).unwrap();
+    /// assert_eq!(start.format("%Y-%m-%d").to_string(), "1991-11-25");
+    /// ```
+    #[must_use]
+    pub fn qualifier_time(&self, pid: Pid) -> Option<DateTime<Utc>> {
+        self.qualifier_pid_claims(pid).find_map(|data| match data {
+            ClaimValueData::DateTime { date_time, .. } => Some(*date_time),
+            _ => None,
+        })
+    }
 }
 
 impl ReferenceGroup {
-    #[must_use]
+    /// Parse a reference group from its Wikibase JSON representation. The claims are in the
+    /// order given by `snaks-order`, or if that's missing, sorted by property ID.
+    ///
+    /// # Errors
+    /// If the JSON can't be parsed to a reference group, an `EntityError` will be returned.
+    pub fn from_json(group: &Value) -> Result<Self, EntityError> {
+        let snaks = group
+            .get("snaks")
+            .ok_or(EntityError::NoReferenceSnaks)?
+            .as_object()
+            .ok_or(EntityError::ExpectedObject)?;
+        let order: Vec<&str> = if let Some(order) = group.get("snaks-order") {
+            order
+                .as_array()
+                .ok_or(EntityError::NoSnakOrder)?
+                .iter()
+                .map(|pid| pid.as_str().ok_or(EntityError::ExpectedPidString))
+                .collect::<Result<_, _>>()?
+        } else {
+            // older dumps and some other Wikibases leave out snaks-order
+            let mut pids = snaks
+                .keys()
+                .map(|pid| Pid::from_str(pid).map(|id| (id, &pid[..])))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| EntityError::BadId)?;
+            pids.sort_unstable();
+            pids.into_iter().map(|(_, pid)| pid).collect()
+        };
+        let mut claims = Vec::with_capacity(snaks.len());
+        for pid in order {
+            let pid_id = Pid::from_str(pid).map_err(|_| EntityError::BadId)?;
+            for subsnak in snaks
+                .get(pid)
+                .ok_or(EntityError::SnaksOrderIncludesNonSnak)?
+                .as_array()
+                .ok_or(EntityError::ExpectedReferenceArray)?
+            {
+                claims.push((pid_id, ClaimValueData::parse_snak(subsnak.clone())?));
+            }
+        }
+        claims.shrink_to_fit();
+        Ok(Self {
+            claims,
+            hash: group
+                .get("hash")
+                .ok_or(EntityError::NoHash)?
+                .as_str()
+                .ok_or(EntityError::ExpectedHashString)?
+                .to_string(),
+        })
+    }
+
     /// Returns an iterator of references to all the claim data for a property ID.
     ///
     /// ## Example
@@ -967,19 +3895,153 @@ impl ReferenceGroup {
     /// };
     /// assert_eq!(claims.next(), None);
     /// ```
-
     pub fn pid_claims(&self, pid: Pid) -> impl Iterator<Item = &ClaimValueData> {
         self.claims
             .iter()
             .filter(move |(claim_pid, _)| *claim_pid == pid)
             .map(|(_, value)| value)
     }
+
+    /// Get the first claim data for a property ID, in the order of [`ReferenceGroup::claims`].
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    /// # let q42 = wikidata::Entity::from_json(j).unwrap();
+    /// use wikidata::{consts, ClaimValueData};
+    /// let group = &q42.claim_by_id("q42$881F40DC-0AFE-4FEB-B882-79600D234273").unwrap().1.references[0];
+    /// assert_eq!(
+    ///     group.claim_for(consts::REFERENCE_URL),
+    ///     Some(&ClaimValueData::Url("http://highgatecemetery.org/visit/who".to_string())),
+    /// );
+    /// assert_eq!(
+    ///     group.claim_for(consts::STATED_IN),
+    ///     Some(&ClaimValueData::Item(wikidata::Qid(533697))),
+    /// );
+    /// assert_eq!(group.claim_for(consts::INSTANCE_OF), None);
+    /// ```
+    #[must_use]
+    pub fn claim_for(&self, pid: Pid) -> Option<&ClaimValueData> {
+        self.pid_claims(pid).next()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn entity_at(lat: f64, lon: f64) -> Entity {
+        Entity {
+            id: WikiId::EntityId(Qid(1)),
+            claims: vec![(
+                consts::COORDINATE_LOCATION,
+                ClaimValue {
+                    data: ClaimValueData::GlobeCoordinate {
+                        lat,
+                        lon,
+                        precision: Some(0.01),
+                        globe: consts::EARTH.into(),
+                    },
+                    ..ClaimValue::default()
+                },
+            )],
+            entity_type: EntityType::Entity,
+            descriptions: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            sitelinks: BTreeMap::new(),
+            lexeme: None,
+        }
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        assert_eq!(bounding_box(&[]), None);
+        assert_eq!(
+            bounding_box(&[
+                entity_at(51.5, -0.1),
+                entity_at(48.9, 2.4),
+                entity_at(52.5, 13.4)
+            ]),
+            Some((48.9, -0.1, 52.5, 13.4))
+        );
+        // Fiji and Samoa, across the antimeridian
+        assert_eq!(
+            bounding_box(&[entity_at(-18.1, 178.4), entity_at(-13.8, -171.8)]),
+            Some((-18.1, 178.4, -13.8, -171.8))
+        );
+    }
+
+    #[test]
+    fn hashing() {
+        use std::collections::{hash_map::DefaultHasher, HashSet};
+        let hash = |x: &ClaimValueData| {
+            let mut hasher = DefaultHasher::new();
+            x.hash(&mut hasher);
+            hasher.finish()
+        };
+        let coordinate = |lat| ClaimValueData::GlobeCoordinate {
+            lat,
+            lon: 0.0,
+            precision: None,
+            globe: consts::EARTH.into(),
+        };
+        assert_eq!(coordinate(0.0), coordinate(-0.0));
+        assert_eq!(hash(&coordinate(0.0)), hash(&coordinate(-0.0)));
+        assert_ne!(hash(&coordinate(0.0)), hash(&coordinate(1.0)));
+
+        let mut entities = HashSet::new();
+        assert!(entities.insert(entity_at(51.5, -0.1)));
+        assert!(!entities.insert(entity_at(51.5, -0.1)));
+        assert!(entities.insert(entity_at(48.9, 2.4)));
+        assert_eq!(entities.len(), 2);
+    }
+
+    #[test]
+    fn best_claims() {
+        let population = |amount, rank| {
+            (
+                consts::POPULATION,
+                ClaimValue {
+                    data: ClaimValueData::Quantity {
+                        amount,
+                        lower_bound: None,
+                        upper_bound: None,
+                        unit: None,
+                    },
+                    rank,
+                    ..ClaimValue::default()
+                },
+            )
+        };
+        let mut entity = entity_at(0.0, 0.0);
+        entity.claims = vec![
+            population(9_800_000.0, Rank::Normal),
+            population(10_300_000.0, Rank::Preferred),
+            population(10_100_000.0, Rank::Normal),
+            population(1.0, Rank::Deprecated),
+        ];
+        let best = entity.best_claims(consts::POPULATION);
+        assert_eq!(best, vec![&entity.claims[1].1]);
+        assert_eq!(entity.get_quantity(consts::POPULATION), Some(10_300_000.0));
+        assert_eq!(
+            entity.best_statement(consts::POPULATION),
+            Some(&entity.claims[1].1)
+        );
+
+        entity.claims.remove(1);
+        assert_eq!(
+            entity.best_claims(consts::POPULATION),
+            vec![&entity.claims[0].1, &entity.claims[1].1]
+        );
+        assert_eq!(entity.get_quantity(consts::POPULATION), Some(9_800_000.0));
+
+        entity.claims.drain(..2);
+        assert!(entity.best_claims(consts::POPULATION).is_empty());
+        assert_eq!(entity.best_statement(consts::POPULATION), None);
+        assert_eq!(entity.get_quantity(consts::POPULATION), None);
+    }
+
     #[test]
     fn time_parsing() {
         let valid_times = vec![
@@ -994,14 +4056,16 @@ mod test {
             "-12561",
             "+311-12-31T12:34:56Z",
             "+311-12-31T23:45:42Z",
+            "+311-12-31T12:34:56.5Z",
+            "+311-12-31T12:34:56",
             // below are times that *should* work, but chrono doesn't accept
             // "-410000000-00-00T00:00:00Z",
         ];
         for time in valid_times {
-            println!("Trying \"{}\"", time);
-            assert!(match parse_wb_time(time) {
+            println!("Trying \"{time}\"");
+            assert!(match parse_wb_time(time, Utc.fix()) {
                 Ok(val) => {
-                    println!("Got {:#?}", val);
+                    println!("Got {val:#?}");
                     true
                 }
                 Err(_) => false,
@@ -1009,12 +4073,203 @@ mod test {
         }
     }
 
+    #[test]
+    fn time_parsing_seconds() {
+        let expected = parse_wb_time("+2001-12-31T12:34:56Z", Utc.fix()).unwrap();
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:56", Utc.fix()),
+            Ok(expected)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:56.5Z", Utc.fix()),
+            Ok(expected)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:56.999", Utc.fix()),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn time_parsing_malformed() {
+        assert_eq!(parse_wb_time("", Utc.fix()), Err(EntityError::TimeEmpty));
+        assert_eq!(
+            parse_wb_time("é2001-12-31", Utc.fix()),
+            Err(EntityError::NoDateYear)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:Z", Utc.fix()),
+            Err(EntityError::FloatParse)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:é", Utc.fix()),
+            Err(EntityError::FloatParse)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34", Utc.fix()),
+            Err(EntityError::MissingSecond)
+        );
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:5Z", Utc.fix()),
+            Ok(Utc.with_ymd_and_hms(2001, 12, 31, 12, 34, 5).unwrap())
+        );
+    }
+
+    #[test]
+    fn time_parsing_offset() {
+        let expected = Utc.with_ymd_and_hms(2001, 12, 31, 11, 4, 56).unwrap();
+        let offset = |minutes: i64| parse_wb_timezone(&Value::from(minutes));
+        assert_eq!(
+            parse_wb_time("+2001-12-31T12:34:56Z", offset(90).unwrap()),
+            Ok(expected)
+        );
+        let expected = Utc.with_ymd_and_hms(2002, 1, 1, 2, 34, 56).unwrap();
+        assert_eq!(
+            parse_wb_time("+2001-12-31T21:34:56Z", offset(-300).unwrap()),
+            Ok(expected)
+        );
+        assert_eq!(
+            parse_wb_time("+262142-12-31T23:00:00Z", offset(-300).unwrap()),
+            Err(EntityError::NoDateMatched)
+        );
+        assert_eq!(
+            parse_wb_time("-262143-01-01T01:00:00Z", offset(300).unwrap()),
+            Err(EntityError::NoDateMatched)
+        );
+        assert_eq!(offset(1440), Err(EntityError::InvalidTimezone));
+        assert_eq!(offset(-1440), Err(EntityError::InvalidTimezone));
+        assert_eq!(
+            parse_wb_timezone(&Value::from(1.5)),
+            Err(EntityError::InvalidTimezone)
+        );
+        assert_eq!(parse_wb_timezone(&Value::Null), Ok(Utc.fix()));
+    }
+
     #[test]
     fn as_qid_test() {
         let qid = try_get_as_qid(
             &serde_json::from_str(r#""http://www.wikidata.org/entity/Q1234567""#).unwrap(),
         );
-        assert_eq!(qid, Ok(Qid(1234567)));
+        assert_eq!(qid, Ok(Qid(1_234_567)));
+    }
+
+    #[test]
+    fn reference_without_snaks_order() {
+        let url_snak = serde_json::json!({
+            "snaktype": "value",
+            "property": "P854",
+            "datavalue": { "value": "https://example.com/", "type": "string" },
+            "datatype": "url"
+        });
+        let item_snak = serde_json::json!({
+            "snaktype": "value",
+            "property": "P248",
+            "datavalue": {
+                "value": { "entity-type": "item", "numeric-id": 5, "id": "Q5" },
+                "type": "wikibase-entityid"
+            },
+            "datatype": "wikibase-item"
+        });
+        let group = serde_json::json!({
+            "hash": "abc",
+            "snaks": { "P854": [url_snak], "P248": [item_snak] }
+        });
+        let expected = ReferenceGroup {
+            claims: vec![
+                (Pid(248), ClaimValueData::Item(Qid(5))),
+                (
+                    Pid(854),
+                    ClaimValueData::Url("https://example.com/".to_string()),
+                ),
+            ],
+            hash: "abc".to_string(),
+        };
+        assert_eq!(ReferenceGroup::from_json(&group), Ok(expected.clone()));
+
+        let claim = serde_json::json!({
+            "mainsnak": item_snak,
+            "type": "statement",
+            "id": "Q1$abc",
+            "rank": "normal",
+            "references": [group]
+        });
+        let claim = ClaimValue::get_prop_from_snak(claim, false, true).unwrap();
+        assert_eq!(claim.references, vec![expected]);
+    }
+
+    #[test]
+    fn deprecated_prop_from_snak() {
+        let claim = serde_json::json!({
+            "mainsnak": {
+                "snaktype": "value",
+                "property": "P31",
+                "datatype": "wikibase-item",
+                "datavalue": {
+                    "type": "wikibase-entityid",
+                    "value": { "entity-type": "item", "numeric-id": 5, "id": "Q5" }
+                }
+            },
+            "type": "statement",
+            "id": "Q1$abc",
+            "rank": "deprecated"
+        });
+        let parsed = ClaimValue::get_prop_from_snak(claim.clone(), true, false).unwrap();
+        assert_eq!(parsed.rank, Rank::Deprecated);
+        assert_eq!(parsed.data, ClaimValueData::Item(Qid(5)));
+        assert_eq!(parsed.id, "");
+        assert_eq!(ClaimValue::get_prop_from_snak(claim, true, true), None);
+    }
+
+    #[test]
+    fn canonicalize() {
+        let j: Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+        let mut q42 = Entity::from_json(j).unwrap();
+        let mut reversed = q42.clone();
+        reversed.claims.reverse();
+        for (_, claim) in &mut reversed.claims {
+            claim.references.reverse();
+        }
+        for aliases in reversed.aliases.values_mut() {
+            aliases.reverse();
+        }
+        q42.canonicalize();
+        reversed.canonicalize();
+        assert_eq!(q42, reversed);
+        assert!(q42.claims.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn globe_and_unit() {
+        assert_eq!(parse_wb_globe(&Value::Null), Ok(consts::EARTH.into()));
+        assert_eq!(
+            parse_wb_globe(&serde_json::json!("http://www.wikidata.org/entity/Q405")),
+            Ok(EntityOrIri::Entity(Qid(405)))
+        );
+        assert_eq!(
+            parse_wb_globe(&serde_json::json!("http://example.com/moon")),
+            Ok(EntityOrIri::Iri("http://example.com/moon".to_string()))
+        );
+        assert_eq!(
+            parse_wb_globe(&serde_json::json!(5)),
+            Err(EntityError::ExpectedUriString)
+        );
+
+        assert_eq!(parse_wb_unit(&Value::Null), Ok(None));
+        assert_eq!(parse_wb_unit(&serde_json::json!("1")), Ok(None));
+        assert_eq!(
+            parse_wb_unit(&serde_json::json!("http://www.wikidata.org/entity/Q11573")),
+            Ok(Some(consts::METRE.into()))
+        );
+        assert_eq!(
+            parse_wb_unit(&serde_json::json!("http://example.com/unit")),
+            Ok(Some(EntityOrIri::Iri(
+                "http://example.com/unit".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_wb_unit(&serde_json::json!(5)),
+            Err(EntityError::ExpectedUriString)
+        );
     }
 
     #[test]
@@ -1027,5 +4282,17 @@ mod test {
             Ok(-81.12683)
         );
         assert_eq!(parse_wb_number(&serde_json::json!("+0")), Ok(0.));
+        assert_eq!(
+            parse_wb_number(&serde_json::json!("NaN")),
+            Err(EntityError::FloatParse)
+        );
+        assert_eq!(
+            parse_wb_number(&serde_json::json!("+inf")),
+            Err(EntityError::FloatParse)
+        );
+        assert_eq!(
+            parse_wb_number(&serde_json::json!("1e999")),
+            Err(EntityError::FloatParse)
+        );
     }
 }
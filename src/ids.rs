@@ -5,9 +5,9 @@ use std::{fmt, num::ParseIntError, str::FromStr};
 
 pub mod consts;
 
-/// Three main types of IDs entities can have.
+/// The types of IDs entities can have.
 ///
-/// EntitySchemas (with E IDs) are currently unsupported.
+/// `EntitySchemas` (with E IDs) are currently unsupported.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum WikiId {
@@ -17,6 +17,12 @@ pub enum WikiId {
     PropertyId(Pid),
     /// An Lid, representing a lexeme.
     LexemeId(Lid),
+    /// A Mid, representing a Wikimedia Commons media file.
+    MediaInfoId(Mid),
+    /// A Fid, representing a form of a lexeme.
+    FormId(Fid),
+    /// A Sid, representing a sense of a lexeme.
+    SenseId(Sid),
 }
 
 impl FromStr for WikiId {
@@ -24,15 +30,131 @@ impl FromStr for WikiId {
 
     /// Parse the identifier from a string.
     fn from_str(x: &str) -> Result<Self, Self::Err> {
+        if x.trim().is_empty() {
+            return Err(IdParseError::Empty);
+        }
         match x.chars().next() {
             Some('Q') => Qid::from_str(x).map(WikiId::EntityId),
             Some('P') => Pid::from_str(x).map(WikiId::PropertyId),
-            Some('L') => Lid::from_str(x).map(WikiId::LexemeId),
+            // forms and senses are written like `L5-F9` and `L5-S9`
+            Some('L') => match x.split_once('-').map(|(_, subid)| subid.chars().next()) {
+                None => Lid::from_str(x).map(WikiId::LexemeId),
+                Some(Some('S')) => Sid::from_str(x).map(WikiId::SenseId),
+                Some(_) => Fid::from_str(x).map(WikiId::FormId),
+            },
+            Some('M') => Mid::from_str(x).map(WikiId::MediaInfoId),
             _ => Err(IdParseError::InvalidPrefix),
         }
     }
 }
 
+impl fmt::Display for WikiId {
+    /// Display the inner ID, like `Q42` or `L1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WikiId::EntityId(id) => id.fmt(f),
+            WikiId::PropertyId(id) => id.fmt(f),
+            WikiId::LexemeId(id) => id.fmt(f),
+            WikiId::MediaInfoId(id) => id.fmt(f),
+            WikiId::FormId(id) => id.fmt(f),
+            WikiId::SenseId(id) => id.fmt(f),
+        }
+    }
+}
+
+/// The most IDs the `wbgetentities` API accepts in one request.
+const WBGETENTITIES_BATCH_SIZE: usize = 50;
+
+/// Build `wbgetentities` API URLs to fetch many entities from Wikidata with few requests. The IDs
+/// are split into batches of 50, the most the API accepts at once, with one URL per batch. The
+/// response to each URL is a multi-entity object with an `entities` key.
+///
+/// All of the URLs are for the Wikidata API, so [`MediaInfoId`](WikiId::MediaInfoId)s won't be
+/// found; those need to be fetched from Wikimedia Commons.
+///
+/// ## Example
+/// ```
+/// use wikidata::{wbgetentities_url, Pid, Qid, WikiId};
+/// let urls = wbgetentities_url(&[WikiId::EntityId(Qid(42)), WikiId::PropertyId(Pid(31))]);
+/// assert_eq!(
+///     urls,
+///     vec!["https://www.wikidata.org/w/api.php?action=wbgetentities&ids=Q42|P31&format=json"]
+/// );
+/// ```
+#[must_use]
+pub fn wbgetentities_url(ids: &[WikiId]) -> Vec<String> {
+    ids.chunks(WBGETENTITIES_BATCH_SIZE)
+        .map(|batch| {
+            let ids: Vec<String> = batch.iter().map(ToString::to_string).collect();
+            format!(
+                "https://www.wikidata.org/w/api.php?action=wbgetentities&ids={}&format=json",
+                ids.join("|")
+            )
+        })
+        .collect()
+}
+
+/// A reference to a Wikidata entity, or to something else by its IRI. Globes and units of
+/// measurement are usually Wikidata entities, but they *can* be any IRI, and are on some other
+/// Wikibase instances.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityOrIri {
+    /// A Wikidata entity.
+    Entity(Qid),
+    /// Any other IRI.
+    Iri(String),
+}
+
+impl EntityOrIri {
+    /// Get the Qid, if this is a Wikidata entity.
+    #[must_use]
+    pub fn as_qid(&self) -> Option<Qid> {
+        match self {
+            EntityOrIri::Entity(qid) => Some(*qid),
+            EntityOrIri::Iri(_) => None,
+        }
+    }
+
+    /// Get the full IRI, like `http://www.wikidata.org/entity/Q2` for Wikidata entities.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{EntityOrIri, Qid};
+    /// assert_eq!(EntityOrIri::Entity(Qid(2)).to_iri(), "http://www.wikidata.org/entity/Q2");
+    /// let moon = EntityOrIri::Iri("http://example.org/moon".to_string());
+    /// assert_eq!(moon.to_iri(), "http://example.org/moon");
+    /// ```
+    #[must_use]
+    pub fn to_iri(&self) -> String {
+        match self {
+            EntityOrIri::Entity(qid) => format!("http://www.wikidata.org/entity/{qid}"),
+            EntityOrIri::Iri(iri) => iri.clone(),
+        }
+    }
+}
+
+impl From<Qid> for EntityOrIri {
+    fn from(qid: Qid) -> Self {
+        EntityOrIri::Entity(qid)
+    }
+}
+
+impl PartialEq<Qid> for EntityOrIri {
+    fn eq(&self, other: &Qid) -> bool {
+        self.as_qid() == Some(*other)
+    }
+}
+
+impl fmt::Display for EntityOrIri {
+    /// Display the Qid for Wikidata entities, and the full IRI otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityOrIri::Entity(qid) => qid.fmt(f),
+            EntityOrIri::Iri(iri) => f.write_str(iri),
+        }
+    }
+}
+
 /// An error parsing an ID.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdParseError {
@@ -58,10 +180,41 @@ pub enum IdParseError {
     /// assert_eq!(Fid::from_str("L3"), Err(IdParseError::TooFewParts));
     /// ```
     TooFewParts,
+    /// The ID was empty, only whitespace, or only a prefix letter with no number
+    ///
+    /// ## Example
+    /// ```
+    /// use std::str::FromStr;
+    /// use wikidata::{Qid, IdParseError};
+    /// assert_eq!(Qid::from_str(""), Err(IdParseError::Empty));
+    /// assert_eq!(Qid::from_str("Q"), Err(IdParseError::Empty));
+    /// ```
+    Empty,
+}
+
+impl fmt::Display for IdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdParseError::UnparseableNumber(err) => write!(f, "invalid ID number: {err}"),
+            IdParseError::InvalidPrefix => write!(f, "invalid ID prefix"),
+            IdParseError::TooManyParts => write!(f, "ID has too many parts"),
+            IdParseError::TooFewParts => write!(f, "ID has too few parts"),
+            IdParseError::Empty => write!(f, "ID is empty"),
+        }
+    }
+}
+
+impl std::error::Error for IdParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IdParseError::UnparseableNumber(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! id_def {
-    ($name:ident, $full_name:expr, $letter:expr, $khar:expr) => {
+    ($name:ident, $full_name:expr, $letter:expr, $khar:expr, $site:expr, $page_prefix:expr) => {
         #[derive(
             Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
         )]
@@ -70,15 +223,21 @@ macro_rules! id_def {
         pub struct $name(pub u64);
 
         impl $name {
-            /// Get the URL to access data about the claim on Wikidata.
+            /// Get the URL to access data about the entity as JSON.
             #[must_use]
             pub fn json_url(&self) -> String {
                 format!(
-                    concat!(
-                        "https://www.wikidata.org/wiki/Special:EntityData/",
-                        $letter,
-                        "{}.json"
-                    ),
+                    concat!($site, "/wiki/Special:EntityData/", $letter, "{}.json"),
+                    self.0
+                )
+            }
+
+            /// Get the URL of the page a person would visit to see the entity, such as
+            #[doc = concat!("`", $site, "/wiki/", $page_prefix, $letter, "1`.")]
+            #[must_use]
+            pub fn wiki_url(&self) -> String {
+                format!(
+                    concat!($site, "/wiki/", $page_prefix, $letter, "{}"),
                     self.0
                 )
             }
@@ -88,10 +247,16 @@ macro_rules! id_def {
 
             /// Parse the identifier from a string.
             fn from_str(x: &str) -> Result<Self, Self::Err> {
+                if x.trim().is_empty() {
+                    return Err(IdParseError::Empty);
+                }
                 if x.chars().next() != Some($khar) {
                     return Err(IdParseError::InvalidPrefix);
                 }
                 let num_str = &x[1..];
+                if num_str.is_empty() {
+                    return Err(IdParseError::Empty);
+                }
                 match num_str.parse() {
                     Ok(num) => Ok(Self(num)),
                     Err(e) => Err(IdParseError::UnparseableNumber(e)),
@@ -107,9 +272,31 @@ macro_rules! id_def {
     };
 }
 
-id_def!(Qid, "entity ID", "Q", 'Q');
-id_def!(Pid, "property ID", "P", 'P');
-id_def!(Lid, "lexeme ID", "L", 'L');
+id_def!(Qid, "entity ID", "Q", 'Q', "https://www.wikidata.org", "");
+id_def!(
+    Pid,
+    "property ID",
+    "P",
+    'P',
+    "https://www.wikidata.org",
+    "Property:"
+);
+id_def!(
+    Lid,
+    "lexeme ID",
+    "L",
+    'L',
+    "https://www.wikidata.org",
+    "Lexeme:"
+);
+id_def!(
+    Mid,
+    "Commons media file ID",
+    "M",
+    'M',
+    "https://commons.wikimedia.org",
+    "Special:EntityPage/"
+);
 
 macro_rules! lexeme_subid_def {
     ($name:ident, $full_name:expr, $letter:expr, $khar:expr) => {
@@ -120,6 +307,30 @@ macro_rules! lexeme_subid_def {
         )]
         pub struct $name(pub Lid, pub u16);
 
+        impl $name {
+            /// Get the URL to access data about the ID on Wikidata. This is the URL for the
+            /// parent lexeme, since forms and senses are only available as part of their lexeme.
+            #[must_use]
+            pub fn json_url(&self) -> String {
+                self.0.json_url()
+            }
+
+            /// Get the URL of the parent lexeme's page, anchored to the ID, such as
+            #[doc = concat!("`https://www.wikidata.org/wiki/Lexeme:L1#L1-", $letter, "2`.")]
+            #[must_use]
+            pub fn wiki_url(&self) -> String {
+                format!("{}#{}", self.0.wiki_url(), self.fragment())
+            }
+
+            /// Get the ID as it's used for the fragment of a URL anchoring to it on its lexeme's
+            /// page, such as
+            #[doc = concat!("`L1-", $letter, "2`.")]
+            #[must_use]
+            pub fn fragment(&self) -> String {
+                self.to_string()
+            }
+        }
+
         impl fmt::Display for $name {
             /// Display the ID as it would be in a URI.
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -132,9 +343,15 @@ macro_rules! lexeme_subid_def {
 
             /// Parse the identifier from a string.
             fn from_str(x: &str) -> Result<Self, Self::Err> {
+                if x.trim().is_empty() {
+                    return Err(IdParseError::Empty);
+                }
                 if x.chars().next() != Some('L') {
                     return Err(IdParseError::InvalidPrefix);
                 }
+                if x.len() == 1 {
+                    return Err(IdParseError::Empty);
+                }
                 let mut parts = x[1..].split('-');
                 let lid = parts
                     .next()
@@ -162,7 +379,7 @@ lexeme_subid_def!(Fid, "form ID", "F", 'F');
 lexeme_subid_def!(Sid, "sense ID", "S", 'S');
 
 #[cfg(test)]
-pub mod test {
+mod test {
     use super::*;
 
     #[test]
@@ -178,35 +395,112 @@ pub mod test {
         assert_eq!(
             Lid(1).json_url(),
             "https://www.wikidata.org/wiki/Special:EntityData/L1.json"
-        )
+        );
+        assert_eq!(
+            Fid(Lid(1), 2).json_url(),
+            "https://www.wikidata.org/wiki/Special:EntityData/L1.json"
+        );
+        assert_eq!(
+            Sid(Lid(3), 4).json_url(),
+            "https://www.wikidata.org/wiki/Special:EntityData/L3.json"
+        );
+        assert_eq!(
+            Mid(8).json_url(),
+            "https://commons.wikimedia.org/wiki/Special:EntityData/M8.json"
+        );
+    }
+
+    #[test]
+    fn wiki_url() {
+        assert_eq!(Qid(42).wiki_url(), "https://www.wikidata.org/wiki/Q42");
+        assert_eq!(
+            Pid(31).wiki_url(),
+            "https://www.wikidata.org/wiki/Property:P31"
+        );
+        assert_eq!(Lid(2).wiki_url(), "https://www.wikidata.org/wiki/Lexeme:L2");
+        assert_eq!(
+            Sid(Lid(5), 9).wiki_url(),
+            "https://www.wikidata.org/wiki/Lexeme:L5#L5-S9"
+        );
+        assert_eq!(
+            Fid(Lid(3), 11).wiki_url(),
+            "https://www.wikidata.org/wiki/Lexeme:L3#L3-F11"
+        );
+        assert_eq!(
+            Mid(74_698_470).wiki_url(),
+            "https://commons.wikimedia.org/wiki/Special:EntityPage/M74698470"
+        );
+    }
+
+    #[test]
+    fn wbgetentities_batches() {
+        assert!(wbgetentities_url(&[]).is_empty());
+        let ids: Vec<WikiId> = (1..=120).map(|n| WikiId::EntityId(Qid(n))).collect();
+        let urls = wbgetentities_url(&ids);
+        assert_eq!(urls.len(), 3);
+        assert!(urls[0].contains("ids=Q1|Q2|"));
+        assert!(urls[0].ends_with("|Q50&format=json"));
+        assert!(urls[1].contains("ids=Q51|"));
+        assert_eq!(
+            urls[2].matches('|').count(),
+            19,
+            "the last batch has the remaining 20 IDs"
+        );
+    }
+
+    #[test]
+    fn fragment() {
+        assert_eq!(Fid(Lid(1), 2).fragment(), "L1-F2");
+        assert_eq!(Sid(Lid(3), 4).fragment(), "L3-S4");
     }
 
     #[test]
     fn to_string() {
         let entity = Qid(42);
-        assert_eq!(format!("{}", entity), "Q42");
+        assert_eq!(format!("{entity}"), "Q42");
 
         let prop = Pid(6);
-        assert_eq!(format!("{}", prop), "P6");
+        assert_eq!(format!("{prop}"), "P6");
 
         let lexeme = Lid(2);
-        assert_eq!(format!("{}", lexeme), "L2");
+        assert_eq!(format!("{lexeme}"), "L2");
+
+        let media = Mid(8);
+        assert_eq!(format!("{media}"), "M8");
 
         let sense = Sid(Lid(5), 9);
-        assert_eq!(format!("{}", sense), "L5-S9");
+        assert_eq!(format!("{sense}"), "L5-S9");
 
         let form = Fid(Lid(3), 11);
-        assert_eq!(format!("{}", form), "L3-F11");
+        assert_eq!(format!("{form}"), "L3-F11");
+    }
+
+    #[test]
+    fn error_display() {
+        use std::error::Error;
+        let err = Qid::from_str("Qx").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid ID number: invalid digit found in string"
+        );
+        assert!(err.source().is_some());
+        assert_eq!(IdParseError::InvalidPrefix.to_string(), "invalid ID prefix");
+        assert!(IdParseError::InvalidPrefix.source().is_none());
     }
 
     #[test]
     fn from_str() {
         assert_eq!(Qid::from_str("Q42").unwrap(), Qid(42));
-        assert_eq!(Lid::from_str("L944114").unwrap(), Lid(944114));
+        assert_eq!(Lid::from_str("L944114").unwrap(), Lid(944_114));
         assert_eq!(Pid::from_str("P1341").unwrap(), Pid(1341));
         assert_eq!(Pid::from_str("Q1341"), Err(IdParseError::InvalidPrefix));
         assert_eq!(Pid::from_str("1341"), Err(IdParseError::InvalidPrefix));
-        assert!(Qid::from_str("Q").is_err());
+        assert_eq!(Qid::from_str("Q"), Err(IdParseError::Empty));
+        assert_eq!(Qid::from_str(""), Err(IdParseError::Empty));
+        assert_eq!(Pid::from_str("  "), Err(IdParseError::Empty));
+        assert_eq!(Sid::from_str("L"), Err(IdParseError::Empty));
+        assert_eq!(WikiId::from_str(""), Err(IdParseError::Empty));
+        assert_eq!(WikiId::from_str("P"), Err(IdParseError::Empty));
         assert_eq!(Sid::from_str("S1341"), Err(IdParseError::InvalidPrefix));
         assert_eq!(Sid::from_str("L1341"), Err(IdParseError::TooFewParts));
         assert_eq!(
@@ -216,8 +510,22 @@ pub mod test {
         assert_eq!(Sid::from_str("L1341-S123").unwrap(), Sid(Lid(1341), 123));
         assert!(Lid::from_str("L1341-S123").is_err());
         assert!(Lid::from_str("L1341-F123").is_err());
-        assert!(WikiId::from_str("L1341-F123").is_err());
-        assert!(WikiId::from_str("L1341-S123").is_err());
+        assert_eq!(
+            WikiId::from_str("L1341-F123").unwrap(),
+            WikiId::FormId(Fid(Lid(1341), 123))
+        );
+        assert_eq!(
+            WikiId::from_str("L1341-S123").unwrap(),
+            WikiId::SenseId(Sid(Lid(1341), 123))
+        );
+        assert_eq!(
+            WikiId::from_str("L1341-A123"),
+            Err(IdParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            WikiId::from_str("L1341-F1-S2"),
+            Err(IdParseError::TooManyParts)
+        );
         assert_eq!(WikiId::from_str("A123"), Err(IdParseError::InvalidPrefix));
         assert_eq!(
             WikiId::from_str("L1341").unwrap(),
@@ -231,11 +539,76 @@ pub mod test {
             WikiId::from_str("P1341").unwrap(),
             WikiId::PropertyId(Pid(1341))
         );
+        assert_eq!(Mid::from_str("M1341").unwrap(), Mid(1341));
+        assert_eq!(
+            WikiId::from_str("M1341").unwrap(),
+            WikiId::MediaInfoId(Mid(1341))
+        );
+        assert_eq!(WikiId::from_str(""), Err(IdParseError::Empty));
+        assert_eq!(WikiId::from_str("X1"), Err(IdParseError::InvalidPrefix));
+        assert_eq!(WikiId::from_str("q1"), Err(IdParseError::InvalidPrefix));
+        assert!(matches!(
+            WikiId::from_str("Qx"),
+            Err(IdParseError::UnparseableNumber(_))
+        ));
+    }
+
+    #[test]
+    fn wiki_id_display() {
+        for id in [
+            WikiId::EntityId(Qid(42)),
+            WikiId::PropertyId(Pid(31)),
+            WikiId::LexemeId(Lid(1)),
+            WikiId::MediaInfoId(Mid(74_698_470)),
+            WikiId::FormId(Fid(Lid(5), 9)),
+            WikiId::SenseId(Sid(Lid(5), 9)),
+        ] {
+            assert_eq!(WikiId::from_str(&id.to_string()), Ok(id));
+        }
+        assert_eq!(WikiId::PropertyId(Pid(31)).to_string(), "P31");
     }
 
     #[test]
     fn unit_suffix() {
+        const SECOND_SUFFIX: Option<&str> = consts::SECOND.unit_suffix();
         assert_eq!(consts::unit_suffix(consts::METRE).unwrap(), " m");
         assert_eq!(consts::unit_suffix(consts::DEGREE).unwrap(), "°");
+        assert_eq!(consts::unit_suffix(consts::KILOGRAM).unwrap(), " kg");
+        assert_eq!(consts::unit_suffix(consts::KELVIN).unwrap(), " K");
+        assert_eq!(
+            consts::unit_suffix(consts::UNITED_STATES_DOLLAR).unwrap(),
+            " USD"
+        );
+        // the lowest and highest IDs in the table
+        assert_eq!(consts::unit_suffix(consts::LIGHT_YEAR).unwrap(), " ly");
+        assert_eq!(
+            consts::unit_suffix(consts::MILLIGRAM_PER_KILOGRAM).unwrap(),
+            " mg/kg"
+        );
+        assert_eq!(consts::unit_suffix(consts::HUMAN), None);
+        assert_eq!(consts::unit_suffix(Qid(u64::MAX)), None);
+        assert_eq!(SECOND_SUFFIX, Some(" s"));
+    }
+
+    #[test]
+    fn si_conversion() {
+        assert_eq!(consts::si_conversion(consts::KILOMETRE), Some((1e3, "m")));
+        assert_eq!(consts::si_conversion(consts::HOUR), Some((3_600.0, "s")));
+        assert_eq!(consts::si_conversion(consts::TONNE), Some((1e3, "kg")));
+        assert_eq!(consts::si_conversion(consts::DEGREE_CELSIUS), None);
+        assert_eq!(consts::si_conversion(consts::HUMAN), None);
+        // units with a suffix that convert by a plain factor can be converted too
+        for unit in [
+            consts::KILOPARSEC,
+            consts::MEGAPARSEC,
+            consts::MICROGRAM,
+            consts::TONNE,
+            consts::ANNUM,
+            consts::DALTON,
+        ] {
+            assert!(consts::unit_suffix(unit).is_some());
+            assert!(consts::si_conversion(unit).is_some(), "{}", unit);
+        }
+        assert_eq!(consts::si_conversion(Qid(u64::MAX)), None);
     }
 }
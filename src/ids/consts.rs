@@ -10,6 +10,19 @@ macro_rules! qid_consts (
             #[doc = concat!("Item [Q", $value, "](https://www.wikidata.org/wiki/Q", $value, ") on Wikidata")]
             pub const $key: crate::ids::Qid = crate::ids::Qid($value);
         )+
+
+        /// The name of the constant for a Qid, like `HUMAN`, if there is one.
+        #[must_use]
+        pub(crate) const fn qid_const_name(qid: crate::ids::Qid) -> Option<&'static str> {
+            $(
+                if qid.0 == $value {
+                    Some(stringify!($key))
+                } else
+            )+
+            {
+                None
+            }
+        }
     };
 );
 macro_rules! pid_consts (
@@ -18,16 +31,101 @@ macro_rules! pid_consts (
             #[doc = concat!("Property [P", $value, "](https://www.wikidata.org/wiki/Property:P", $value, ") on Wikidata")]
             pub const $key: crate::ids::Pid = crate::ids::Pid($value);
         )+
+
+        /// The name of the constant for a Pid, like `INSTANCE_OF`, if there is one.
+        #[must_use]
+        pub(crate) const fn pid_const_name(pid: crate::ids::Pid) -> Option<&'static str> {
+            $(
+                if pid.0 == $value {
+                    Some(stringify!($key))
+                } else
+            )+
+            {
+                None
+            }
+        }
     };
 );
 
 macro_rules! qid_unit_suffixes {
     { $($key:ident => $value:expr),+, } => {
         use super::*;
+        /// Units and their suffixes, sorted by ID so they can be binary searched.
+        const UNIT_SUFFIXES: &[(Qid, &str)] = &sort_by_id([$(($key, $value)),+]);
+
         #[must_use]
         pub(crate) const fn unit_suffix(qid: Qid) -> Option<&'static str> {
+            find_by_id(UNIT_SUFFIXES, qid)
+        }
+    };
+}
+
+/// Sort a lookup table by ID at compile time, so tables can be written in whatever order reads
+/// best.
+const fn sort_by_id<T: Copy, const N: usize>(mut table: [(Qid, T); N]) -> [(Qid, T); N] {
+    let mut i = 1;
+    while i < N {
+        let mut j = i;
+        while j > 0 && table[j - 1].0 .0 > table[j].0 .0 {
+            let swap = table[j];
+            table[j] = table[j - 1];
+            table[j - 1] = swap;
+            j -= 1;
+        }
+        i += 1;
+    }
+    table
+}
+
+/// Binary search a table sorted with [`sort_by_id`] for an ID.
+const fn find_by_id<T: Copy>(table: &[(Qid, T)], qid: Qid) -> Option<T> {
+    let (mut low, mut high) = (0, table.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let (id, value) = table[mid];
+        if id.0 == qid.0 {
+            return Some(value);
+        } else if id.0 < qid.0 {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    None
+}
+
+macro_rules! qid_si_units {
+    { $($key:ident => $value:expr),+, } => {
+        use super::*;
+        /// Units and their SI conversions, sorted by ID so they can be binary searched.
+        const SI_UNITS: &[(Qid, (f64, &str))] = &sort_by_id([$(($key, $value)),+]);
+
+        #[must_use]
+        pub(crate) const fn si_conversion(qid: Qid) -> Option<(f64, &'static str)> {
+            find_by_id(SI_UNITS, qid)
+        }
+    };
+}
+
+macro_rules! pid_formatter_urls {
+    { $($key:ident => $value:expr),+, } => {
+        use super::*;
+        /// If the Pid is a commonly used external identifier property on Wikidata, get its
+        /// formatter URL, where `$1` is replaced by the identifier to get a link to it.
+        ///
+        /// ## Example
+        /// ```
+        /// use wikidata::consts;
+        /// assert_eq!(
+        ///     consts::identifier_formatter_url(consts::VIAF_ID),
+        ///     Some("https://viaf.org/viaf/$1"),
+        /// );
+        /// assert_eq!(consts::identifier_formatter_url(consts::INSTANCE_OF), None);
+        /// ```
+        #[must_use]
+        pub const fn identifier_formatter_url(pid: Pid) -> Option<&'static str> {
             $(
-                if qid.0 == ($key).0 {
+                if pid.0 == ($key).0 {
                     Some($value)
                 } else
             )+
@@ -52,5 +150,11 @@ pub use qid::*;
 mod qid_unit_suffixes;
 pub(super) use qid_unit_suffixes::*;
 
+mod qid_si_units;
+pub(crate) use qid_si_units::*;
+
 mod pid;
 pub use pid::*;
+
+mod pid_formatter_urls;
+pub use pid_formatter_urls::*;
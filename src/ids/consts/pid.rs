@@ -1,5 +1,6 @@
 pid_consts! {
     INSTANCE_OF => 31,
+    SUBCLASS_OF => 279,
     REFERENCE_URL => 854,
     LANGUAGE => 407, // language of work or name fully
     TITLE => 1476,
@@ -43,4 +44,26 @@ pid_consts! {
     FB_ID => 2013,
     YT_CHANNEL_ID => 2397,
     IG_USERNAME => 2003,
+    COORDINATE_LOCATION => 625,
+    DESCRIBED_AT_URL => 973,
+    DESCRIBED_BY_SOURCE => 1343,
+    POINT_IN_TIME => 585,
+    POPULATION => 1082,
+    COUNTRY => 17,
+    LOCATED_IN_ADMINISTRATIVE_TERRITORIAL_ENTITY => 131,
+    PART_OF => 361,
+    VIAF_ID => 214,
+    GND_ID => 227,
+    LIBRARY_OF_CONGRESS_AUTHORITY_ID => 244,
+    BNF_ID => 268,
+    IDREF_ID => 269,
+    NDL_AUTHORITY_ID => 349,
+    DOI => 356,
+    MUSICBRAINZ_ARTIST_ID => 434,
+    ORCID_ID => 496,
+    FREEBASE_ID => 646,
+    PUBMED_ID => 698,
+    BRITANNICA_ID => 1417,
+    DISCOGS_ARTIST_ID => 1953,
+    GOOGLE_KNOWLEDGE_GRAPH_ID => 2671,
 }
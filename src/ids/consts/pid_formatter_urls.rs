@@ -0,0 +1,21 @@
+// only include common ones, using the formatter URLs from Wikidata
+pid_formatter_urls! {
+    VIAF_ID => "https://viaf.org/viaf/$1",
+    GND_ID => "https://d-nb.info/gnd/$1",
+    LIBRARY_OF_CONGRESS_AUTHORITY_ID => "https://id.loc.gov/authorities/$1",
+    BNF_ID => "https://catalogue.bnf.fr/ark:/12148/cb$1",
+    IDREF_ID => "https://www.idref.fr/$1",
+    NDL_AUTHORITY_ID => "https://id.ndl.go.jp/auth/ndlna/$1",
+    DOI => "https://doi.org/$1",
+    MUSICBRAINZ_ARTIST_ID => "https://musicbrainz.org/artist/$1",
+    ORCID_ID => "https://orcid.org/$1",
+    FREEBASE_ID => "https://www.google.com/search?kgmid=$1",
+    PUBMED_ID => "https://pubmed.ncbi.nlm.nih.gov/$1",
+    BRITANNICA_ID => "https://www.britannica.com/$1",
+    DISCOGS_ARTIST_ID => "https://www.discogs.com/artist/$1",
+    GOOGLE_KNOWLEDGE_GRAPH_ID => "https://www.google.com/search?kgmid=$1",
+    TWITTER_USERNAME => "https://x.com/$1",
+    FB_ID => "https://www.facebook.com/$1",
+    YT_CHANNEL_ID => "https://www.youtube.com/channel/$1",
+    IG_USERNAME => "https://www.instagram.com/$1/",
+}
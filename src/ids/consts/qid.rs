@@ -1,5 +1,7 @@
 qid_consts! {
     EARTH => 2,
+    PROLEPTIC_GREGORIAN_CALENDAR => 1985727,
+    PROLEPTIC_JULIAN_CALENDAR => 1985786,
     HUMAN => 5,
     UNIT_OF_MEASUREMENT => 47574,
     PHYSICAL_QUANTITY => 107715,
@@ -106,6 +108,7 @@ qid_consts! {
     ZEPTOGRAM => 6171168,
     YOCTOGRAM => 6170164,
     POUND => 100995,
+    TONNE => 191118,
     DALTON => 483261,
     DENSITY => 29539,
     KILOGRAM_PER_CUBIC_METRE => 844211,
@@ -159,11 +162,22 @@ qid_consts! {
     JOULE_PER_MOLE => 13035094,
     KILOJOULE_PER_MOLE => 752197,
     KILOJOULE_PER_KILOGRAM => 21077849,
+    NEWTON => 12438,
+    JOULE => 25269,
+    WATT => 25236,
+    AMPERE => 25272,
+    VOLT => 25250,
+    PERCENT => 11229,
     CURRENCY => 8142,
     EURO => 4916,
+    UNITED_STATES_DOLLAR => 4917,
+    POUND_STERLING => 25224,
+    JAPANESE_YEN => 8146,
     CRORE => 1137675,
     INFECTION => 166231,
     DEGREE => 28390,
     BUSINESS => 4830453,
+    SINGULAR => 110786,
+    PLURAL => 146786,
     FICTIONAL_HUMAN => 15632617,
 }
@@ -0,0 +1,84 @@
+// only units that convert to an SI base unit (or a power/ratio of them) by multiplication
+qid_si_units! {
+    METRE => (1.0, "m"),
+    YOTTAMETRE => (1e24, "m"),
+    ZETTAMETRE => (1e21, "m"),
+    EXAMETRE => (1e18, "m"),
+    PETAMETRE => (1e15, "m"),
+    TERAMETRE => (1e12, "m"),
+    GIGAMETRE => (1e9, "m"),
+    MEGAMETRE => (1e6, "m"),
+    MYRIAMETRE => (1e4, "m"),
+    KILOMETRE => (1e3, "m"),
+    HECTOMETRE => (1e2, "m"),
+    DECAMETRE => (1e1, "m"),
+    DECIMETRE => (1e-1, "m"),
+    CENTIMETRE => (1e-2, "m"),
+    MILLIMETRE => (1e-3, "m"),
+    MICROMETRE => (1e-6, "m"),
+    NANOMETRE => (1e-9, "m"),
+    PICOMETRE => (1e-12, "m"),
+    FEMTOMETRE => (1e-15, "m"),
+    ATTOMETRE => (1e-18, "m"),
+    ZEPTOMETRE => (1e-21, "m"),
+    YOCTOMETRE => (1e-24, "m"),
+    PARSEC => (3.085_677_581_491_367e16, "m"),
+    KILOPARSEC => (3.085_677_581_491_367e19, "m"),
+    MEGAPARSEC => (3.085_677_581_491_367e22, "m"),
+    LIGHT_YEAR => (9.460_730_472_580_8e15, "m"),
+    LIGHT_SECOND => (299_792_458.0, "m"),
+    ASTRONOMICAL_UNIT => (149_597_870_700.0, "m"),
+    MILE => (1_609.344, "m"),
+    FOOT => (0.3048, "m"),
+    INCH => (0.0254, "m"),
+    SQUARE_METRE => (1.0, "m²"),
+    SQUARE_KILOMETRE => (1e6, "m²"),
+    SQUARE_CENTIMETRE => (1e-4, "m²"),
+    SQUARE_MILLIMETRE => (1e-6, "m²"),
+    ARE => (1e2, "m²"),
+    HECTARE => (1e4, "m²"),
+    CUBIC_METRE => (1.0, "m³"),
+    CUBIC_KILOMETRE => (1e9, "m³"),
+    CUBIC_DECIMETRE => (1e-3, "m³"),
+    CUBIC_CENTIMETRE => (1e-6, "m³"),
+    CUBIC_MILLIMETRE => (1e-9, "m³"),
+    LITER => (1e-3, "m³"),
+    HECTOLITER => (1e-1, "m³"),
+    DECALITER => (1e-2, "m³"),
+    CENTILITER => (1e-5, "m³"),
+    MILLILITER => (1e-6, "m³"),
+    MICROLITER => (1e-9, "m³"),
+    SECOND => (1.0, "s"),
+    MILLISECOND => (1e-3, "s"),
+    MICROSECOND => (1e-6, "s"),
+    NANOSECOND => (1e-9, "s"),
+    PICOSECOND => (1e-12, "s"),
+    FEMTOSECOND => (1e-15, "s"),
+    ATTOSECOND => (1e-18, "s"),
+    MINUTE => (60.0, "s"),
+    HOUR => (3_600.0, "s"),
+    DAY => (86_400.0, "s"),
+    WEEK => (604_800.0, "s"),
+    // a Julian year, as used in astronomy; calendar years (YEAR) vary in length, so they aren't
+    // here
+    ANNUM => (31_557_600.0, "s"),
+    KILOGRAM => (1.0, "kg"),
+    TONNE => (1e3, "kg"),
+    MEGAGRAM => (1e3, "kg"),
+    HECTOGRAM => (1e-1, "kg"),
+    DECAGRAM => (1e-2, "kg"),
+    GRAM => (1e-3, "kg"),
+    DECIGRAM => (1e-4, "kg"),
+    CENTIGRAM => (1e-5, "kg"),
+    MILLIGRAM => (1e-6, "kg"),
+    MICROGRAM => (1e-9, "kg"),
+    NANOGRAM => (1e-12, "kg"),
+    PICOGRAM => (1e-15, "kg"),
+    POUND => (0.453_592_37, "kg"),
+    DALTON => (1.660_539_066_60e-27, "kg"),
+    KELVIN => (1.0, "K"),
+    KILOGRAM_PER_CUBIC_METRE => (1.0, "kg/m³"),
+    GRAM_PER_CUBIC_CENTIMETRE => (1e3, "kg/m³"),
+    METRE_PER_SECOND => (1.0, "m/s"),
+    KILOMETRE_PER_HOUR => (1.0 / 3.6, "m/s"),
+}
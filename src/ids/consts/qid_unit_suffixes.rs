@@ -2,29 +2,86 @@
 qid_unit_suffixes! {
     METRE => " m",
     KILOMETRE => " km",
+    DECIMETRE => " dm",
     CENTIMETRE => " cm",
     MILLIMETRE => " mm",
+    MICROMETRE => " μm",
+    NANOMETRE => " nm",
+    PICOMETRE => " pm",
+    PARSEC => " pc",
+    MEGAPARSEC => " Mpc",
+    KILOPARSEC => " kpc",
+    LIGHT_YEAR => " ly",
+    ASTRONOMICAL_UNIT => " AU",
+    MILE => " mi",
+    FOOT => " ft",
+    INCH => " in",
     SQUARE_METRE => " m²",
     SQUARE_KILOMETRE => " km²",
     SQUARE_CENTIMETRE => " cm²",
     SQUARE_MILLIMETRE => " mm²",
+    HECTARE => " ha",
     CUBIC_METRE => " m³",
     CUBIC_KILOMETRE => " km³",
+    CUBIC_DECIMETRE => " dm³",
     CUBIC_CENTIMETRE => " cm³",
     CUBIC_MILLIMETRE => " mm³",
+    LITER => " l",
+    MILLILITER => " ml",
+    SECOND => " s",
+    MILLISECOND => " ms",
+    MICROSECOND => " μs",
+    NANOSECOND => " ns",
+    MINUTE => " min",
+    HOUR => " h",
+    DAY => " d",
+    WEEK => " wk",
+    YEAR => " yr",
+    ANNUM => " a",
+    HERTZ => " Hz",
+    KILOHERTZ => " kHz",
+    MEGAHERTZ => " MHz",
+    GIGAHERTZ => " GHz",
+    KILOGRAM => " kg",
     GRAM => " g",
     MILLIGRAM => " mg",
+    MICROGRAM => " μg",
+    TONNE => " t",
+    POUND => " lb",
+    DALTON => " Da",
     KILOGRAM_PER_CUBIC_METRE => " kg/m³",
     GRAM_PER_CUBIC_CENTIMETRE => " g/cm³",
+    GRAM_PER_LITER => " g/l",
     MILLILITRE_PER_LITRE => " ml/l",
-    MILLIGRAM_PER_CUBIC_METER => " mg/cm³",
+    MILLIGRAM_PER_CUBIC_METER => " mg/m³",
     PARTS_PER_MILLION => " ppm",
-    MILLIGRAM_PER_KILOGRAM => " mg/k",
+    PART_PER_BILLION => " ppb",
+    MILLIGRAM_PER_KILOGRAM => " mg/kg",
     GRAM_PER_KILOGRAM => " g/kg",
+    PERCENT => "%",
     DEGREE_CELSIUS => " °C",
-    KELVIN => " °K",
+    KELVIN => " K",
     DEGREE_FAHRENHEIT => " °F",
+    ATMOSPHERE => " atm",
+    BAR => " bar",
+    PASCAL => " Pa",
+    KILOPASCAL => " kPa",
+    HECTOPASCAL => " hPa",
+    MEGAPASCAL => " MPa",
+    TORR => " Torr",
+    MILLIMETER_OF_MERCURY => " mmHg",
     KILOMETRE_PER_HOUR => " km/h",
-    ASTRONOMICAL_UNIT => " AU",
+    METRE_PER_SECOND => " m/s",
+    KNOT => " kn",
+    NEWTON => " N",
+    JOULE => " J",
+    KILOJOULE_PER_MOLE => " kJ/mol",
+    WATT => " W",
+    AMPERE => " A",
+    VOLT => " V",
+    UNITED_STATES_DOLLAR => " USD",
+    EURO => " EUR",
+    POUND_STERLING => " GBP",
+    JAPANESE_YEN => " JPY",
     DEGREE => "°",
 }
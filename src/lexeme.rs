@@ -0,0 +1,248 @@
+//! The parts of lexemes that other entities don't have.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::entity::{parse_claims, parse_terms, term_with_fallback, ClaimValue, EntityError};
+use crate::ids::{Fid, Pid, Qid, Sid};
+use crate::text::Lang;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The data of a lexeme that other entities don't have, as stored in [`Entity::lexeme`].
+///
+/// [`Entity::lexeme`]: crate::Entity::lexeme
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LexemeData {
+    /// The lemmas of the lexeme, the forms used to look it up in a dictionary, in each spelling
+    /// variant. Blank lemmas are left out when parsing.
+    pub lemmas: BTreeMap<Lang, String>,
+    /// The language the lexeme is in, like [English](https://www.wikidata.org/wiki/Q1860).
+    pub language: Qid,
+    /// The lexical category of the lexeme, like [noun](https://www.wikidata.org/wiki/Q1084).
+    pub lexical_category: Qid,
+    /// The forms of the lexeme, in order.
+    pub forms: Vec<Form>,
+    /// The senses of the lexeme, in order.
+    pub senses: Vec<Sense>,
+}
+
+/// A form of a lexeme, such as the plural of a noun.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Form {
+    /// The ID of the form.
+    pub id: Fid,
+    /// How the form is written, in each spelling variant.
+    pub representations: BTreeMap<Lang, String>,
+    /// The grammatical features of the form, like
+    /// [plural](https://www.wikidata.org/wiki/Q146786).
+    pub grammatical_features: Vec<Qid>,
+    /// The claims on the form.
+    pub claims: Vec<(Pid, ClaimValue)>,
+}
+
+impl Form {
+    /// How the form is written in a spelling variant, like `en` or `en-gb`.
+    #[must_use]
+    pub fn representation(&self, lang: &str) -> Option<&str> {
+        self.representations
+            .get(&Lang(lang.to_string()))
+            .map(String::as_str)
+    }
+
+    /// How the form is written in the first of `langs` it has a representation in, falling back
+    /// to `mul` the same way as [`Entity::label_with_fallback`]. Blank representations are
+    /// skipped.
+    ///
+    /// [`Entity::label_with_fallback`]: crate::Entity::label_with_fallback
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    /// # let cat = wikidata::Entity::from_json(j).unwrap().lexeme.unwrap();
+    /// let form = &cat.forms[1];
+    /// assert_eq!(form.representation_with_fallback(&["en-gb", "en"]), Some("cats"));
+    /// assert_eq!(form.representation_with_fallback(&["de"]), None);
+    /// ```
+    #[must_use]
+    pub fn representation_with_fallback(&self, langs: &[&str]) -> Option<&str> {
+        term_with_fallback(&self.representations, langs)
+    }
+
+    /// Whether the form has every one of the grammatical features.
+    #[must_use]
+    pub fn has_features(&self, features: &[Qid]) -> bool {
+        features
+            .iter()
+            .all(|feature| self.grammatical_features.contains(feature))
+    }
+}
+
+/// A sense of a lexeme: one of its meanings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Sense {
+    /// The ID of the sense.
+    pub id: Sid,
+    /// Short descriptions of the meaning, in any number of languages.
+    pub glosses: BTreeMap<Lang, String>,
+    /// The claims on the sense.
+    pub claims: Vec<(Pid, ClaimValue)>,
+}
+
+impl LexemeData {
+    /// Get a form by its ID.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    /// # let cat = wikidata::Entity::from_json(j).unwrap().lexeme.unwrap();
+    /// use wikidata::{Fid, Lid};
+    /// let form = cat.form_by_id(Fid(Lid(7), 2)).unwrap();
+    /// assert_eq!(form.representation("en"), Some("cats"));
+    /// assert_eq!(cat.form_by_id(Fid(Lid(7), 3)), None);
+    /// ```
+    #[must_use]
+    pub fn form_by_id(&self, id: Fid) -> Option<&Form> {
+        self.iter_forms().find(|form| form.id == id)
+    }
+
+    /// Get a sense by its ID.
+    #[must_use]
+    pub fn sense_by_id(&self, id: Sid) -> Option<&Sense> {
+        self.iter_senses().find(|sense| sense.id == id)
+    }
+
+    /// Iterate over the forms of the lexeme, in order.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    /// # let cat = wikidata::Entity::from_json(j).unwrap().lexeme.unwrap();
+    /// let spellings: Vec<_> = cat
+    ///     .iter_forms()
+    ///     .filter_map(|form| form.representation("en"))
+    ///     .collect();
+    /// assert_eq!(spellings, ["cat", "cats"]);
+    /// ```
+    pub fn iter_forms(&self) -> impl Iterator<Item = &Form> {
+        self.forms.iter()
+    }
+
+    /// Iterate over the senses of the lexeme, in order.
+    pub fn iter_senses(&self) -> impl Iterator<Item = &Sense> {
+        self.senses.iter()
+    }
+
+    /// All of the forms that have every one of the grammatical features, in order.
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    /// # let cat = wikidata::Entity::from_json(j).unwrap().lexeme.unwrap();
+    /// use wikidata::consts;
+    /// let plurals = cat.forms_with_features(&[consts::PLURAL]);
+    /// assert_eq!(plurals.len(), 1);
+    /// assert_eq!(plurals[0].representation("en"), Some("cats"));
+    /// ```
+    #[must_use]
+    pub fn forms_with_features(&self, features: &[Qid]) -> Vec<&Form> {
+        self.forms
+            .iter()
+            .filter(|form| form.has_features(features))
+            .collect()
+    }
+
+    /// Parse the lexeme data out of a lexeme entity's Wikibase JSON representation.
+    pub(crate) fn from_json(json: &mut Value) -> Result<Self, EntityError> {
+        let qid = |key, missing| {
+            let id = json.get(key).ok_or(missing)?;
+            Qid::from_str(id.as_str().ok_or(EntityError::ExpectedQidString)?)
+                .map_err(|_| EntityError::BadId)
+        };
+        let language = qid("language", EntityError::NoLexemeLanguage)?;
+        let lexical_category = qid("lexicalCategory", EntityError::NoLexicalCategory)?;
+
+        let mut forms = Vec::new();
+        for form in sub_entities(json, "forms")? {
+            forms.push(Form {
+                id: sub_entity_id(form)?,
+                representations: parse_terms(form.get("representations"))?,
+                grammatical_features: form
+                    .get("grammaticalFeatures")
+                    .map(|features| {
+                        features
+                            .as_array()
+                            .ok_or(EntityError::ExpectedArray)?
+                            .iter()
+                            .map(|feature| {
+                                let feature =
+                                    feature.as_str().ok_or(EntityError::ExpectedQidString)?;
+                                Qid::from_str(feature).map_err(|_| EntityError::BadId)
+                            })
+                            .collect()
+                    })
+                    .transpose()?
+                    .unwrap_or_default(),
+                claims: parse_claims(form.get_mut("claims"))?,
+            });
+        }
+
+        let mut senses = Vec::new();
+        for sense in sub_entities(json, "senses")? {
+            senses.push(Sense {
+                id: sub_entity_id(sense)?,
+                glosses: parse_terms(sense.get("glosses"))?,
+                claims: parse_claims(sense.get_mut("claims"))?,
+            });
+        }
+
+        Ok(Self {
+            lemmas: parse_terms(json.get("lemmas"))?,
+            language,
+            lexical_category,
+            forms,
+            senses,
+        })
+    }
+}
+
+impl Sense {
+    /// The gloss of the sense in a language.
+    #[must_use]
+    pub fn gloss(&self, lang: &str) -> Option<&str> {
+        self.glosses
+            .get(&Lang(lang.to_string()))
+            .map(String::as_str)
+    }
+
+    /// The gloss of the sense in the first of `langs` it has a gloss in, falling back to `mul`
+    /// the same way as [`Entity::label_with_fallback`]. Blank glosses are skipped.
+    ///
+    /// [`Entity::label_with_fallback`]: crate::Entity::label_with_fallback
+    ///
+    /// ## Example
+    /// ```
+    /// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    /// # let cat = wikidata::Entity::from_json(j).unwrap().lexeme.unwrap();
+    /// let sense = &cat.senses[0];
+    /// assert_eq!(sense.gloss_with_fallback(&["fr", "de", "en"]), Some("Hauskatze"));
+    /// assert_eq!(sense.gloss_with_fallback(&["fr"]), None);
+    /// ```
+    #[must_use]
+    pub fn gloss_with_fallback(&self, langs: &[&str]) -> Option<&str> {
+        term_with_fallback(&self.glosses, langs)
+    }
+}
+
+/// The forms or senses of a lexeme, which are left out of some partial fetches.
+fn sub_entities<'a>(json: &'a mut Value, key: &str) -> Result<&'a mut [Value], EntityError> {
+    match json.get_mut(key) {
+        Some(list) => Ok(list.as_array_mut().ok_or(EntityError::ExpectedArray)?),
+        None => Ok(&mut []),
+    }
+}
+
+fn sub_entity_id<T: FromStr>(json: &Value) -> Result<T, EntityError> {
+    let id = json.get("id").ok_or(EntityError::NoId)?;
+    T::from_str(id.as_str().ok_or(EntityError::NonStringId)?).map_err(|_| EntityError::BadId)
+}
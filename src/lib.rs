@@ -5,7 +5,8 @@
 //! Many items in this crate implement [`serde::Serialize`] and [`serde::Deserialize`]. Note that
 //! the JSON serialization of entities provided by these traits is not the same as the
 //! serialization used by Wikidata in data dumps and `Special:EntityData`, but is instead a
-//! serialization specific to this crate.
+//! serialization specific to this crate. This format may change between versions of this crate;
+//! wrap entities in [`VersionedEntity`] to detect data serialized by an incompatible version.
 
 #![warn(clippy::pedantic)]
 #![warn(missing_docs)]
@@ -15,10 +16,24 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::too_many_lines)]
 
+pub(crate) mod builder;
+pub(crate) mod cache;
 pub(crate) mod entity;
 pub(crate) mod ids;
+pub(crate) mod lexeme;
+pub(crate) mod stream;
 pub(crate) mod text;
+pub(crate) mod time;
+pub(crate) mod truthy;
+pub(crate) mod versioned;
 
+pub use builder::*;
+pub use cache::*;
 pub use entity::*;
 pub use ids::*;
+pub use lexeme::*;
+pub use stream::*;
 pub use text::*;
+pub use time::*;
+pub use truthy::*;
+pub use versioned::*;
@@ -0,0 +1,107 @@
+//! Flattening a stream of entities into a stream of claims.
+
+use crate::entity::{ClaimValue, ClaimValueData, Entity, Rank};
+use crate::ids::{Pid, WikiId};
+
+/// An iterator over every claim of every entity in a stream of entities, such as the one returned
+/// by [`Entity::parse_ndjson`]. Each claim is yielded as a `(WikiId, Pid, ClaimValueData, Rank)`
+/// tuple, where the `WikiId` is the ID of the entity the claim is on. Qualifiers, references and
+/// claim IDs are dropped.
+///
+/// Entities are only taken from the underlying iterator once all of the claims of the previous
+/// entity have been yielded. If the underlying iterator yields an error, it's yielded as-is and
+/// the stream continues with the next entity.
+///
+/// ## Example
+/// ```
+/// use wikidata::{ClaimStream, ClaimValueData, Entity, Pid, Qid, Rank, WikiId};
+/// let ndjson = include_str!("../items/Q42.json").replace('\n', "");
+/// for claim in ClaimStream::new(Entity::parse_ndjson(&ndjson)) {
+///     let (id, pid, data, rank) = claim.unwrap();
+///     assert_eq!(id, WikiId::EntityId(Qid(42)));
+///     if pid == Pid(31) {
+///         assert_eq!(data, ClaimValueData::Item(Qid(5)));
+///         assert_eq!(rank, Rank::Normal);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ClaimStream<I> {
+    entities: I,
+    current: Option<(WikiId, std::vec::IntoIter<(Pid, ClaimValue)>)>,
+}
+
+impl<I, E> ClaimStream<I>
+where
+    I: Iterator<Item = Result<Entity, E>>,
+{
+    /// Create a claim stream from an iterator of entities.
+    pub fn new(entities: I) -> Self {
+        Self {
+            entities,
+            current: None,
+        }
+    }
+}
+
+impl<I, E> Iterator for ClaimStream<I>
+where
+    I: Iterator<Item = Result<Entity, E>>,
+{
+    type Item = Result<(WikiId, Pid, ClaimValueData, Rank), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((id, claims)) = &mut self.current {
+                if let Some((pid, claim)) = claims.next() {
+                    return Some(Ok((*id, pid, claim.data, claim.rank)));
+                }
+            }
+            match self.entities.next()? {
+                Ok(entity) => self.current = Some((entity.id, entity.claims.into_iter())),
+                Err(err) => {
+                    self.current = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::{DumpError, EntityError};
+    use crate::ids::Qid;
+
+    #[test]
+    fn flattens_entities() {
+        let ndjson = r#"{"type": "item", "id": "Q1", "claims": {}}
+not json
+{"type": "item", "id": "Q2", "claims": {"P31": [{"mainsnak": {"snaktype": "novalue", "property": "P31", "datatype": "wikibase-item"}, "rank": "normal", "id": "Q2$1"}, {"mainsnak": {"snaktype": "somevalue", "property": "P31", "datatype": "wikibase-item"}, "rank": "preferred", "id": "Q2$2"}]}}
+"#;
+        let claims: Vec<_> = ClaimStream::new(Entity::parse_ndjson(ndjson)).collect();
+        assert_eq!(
+            claims,
+            vec![
+                Err(DumpError {
+                    line: 2,
+                    id: None,
+                    source: EntityError::InvalidJson
+                }),
+                Ok((
+                    WikiId::EntityId(Qid(2)),
+                    Pid(31),
+                    ClaimValueData::NoValue,
+                    Rank::Normal
+                )),
+                Ok((
+                    WikiId::EntityId(Qid(2)),
+                    Pid(31),
+                    ClaimValueData::UnknownValue,
+                    Rank::Preferred
+                )),
+            ]
+        );
+    }
+}
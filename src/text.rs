@@ -1,9 +1,202 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// A language, as used in the Wikibase data model.
+///
+/// Common languages can be constructed with functions like [`Lang::en`]:
+/// ```
+/// use wikidata::Lang;
+/// assert_eq!(Lang::en(), Lang("en".to_string()));
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Lang(pub String);
 
+macro_rules! lang_constructors {
+    { $($name:ident => $code:literal, $desc:literal),+, } => {
+        impl Lang {
+            $(
+                #[doc = concat!("The ", $desc, " language, `", $code, "`.")]
+                #[must_use]
+                pub fn $name() -> Self {
+                    Self($code.to_string())
+                }
+            )+
+        }
+    };
+}
+
+lang_constructors! {
+    mul => "mul", "multiple languages",
+    en => "en", "English",
+    de => "de", "German",
+    fr => "fr", "French",
+    es => "es", "Spanish",
+    it => "it", "Italian",
+    nl => "nl", "Dutch",
+    pt => "pt", "Portuguese",
+    pl => "pl", "Polish",
+    sv => "sv", "Swedish",
+    ru => "ru", "Russian",
+    uk => "uk", "Ukrainian",
+    ar => "ar", "Arabic",
+    ja => "ja", "Japanese",
+    ko => "ko", "Korean",
+    zh => "zh", "Chinese",
+}
+
+impl Lang {
+    /// Parse and validate a language code, like the ones Wikibase uses for terms and monolingual
+    /// text. Codes are made of subtags separated by dashes: a primary language subtag of 2 to 8
+    /// letters (like `en`, `mul`, `zxx`, or `simple`), followed by any number of subtags of 1 to
+    /// 8 letters or digits (like `zh-hans-cn` or `be-tarask`). The code is lowercased, as
+    /// Wikibase does.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::{Lang, LangError};
+    /// assert_eq!(Lang::parse("zh-Hans"), Ok(Lang("zh-hans".to_string())));
+    /// assert_eq!(Lang::parse("mul"), Ok(Lang::mul()));
+    /// assert_eq!(Lang::parse("en_gb"), Err(LangError::InvalidPrimarySubtag));
+    /// assert_eq!(Lang::parse("en-"), Err(LangError::InvalidSubtag));
+    /// ```
+    ///
+    /// # Errors
+    /// If the code isn't a valid language code, a `LangError` will be returned.
+    pub fn parse(code: &str) -> Result<Self, LangError> {
+        if code.is_empty() {
+            return Err(LangError::Empty);
+        }
+        let mut subtags = code.split('-');
+        let primary = subtags.next().unwrap_or_default();
+        if !(2..=8).contains(&primary.len()) || !primary.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(LangError::InvalidPrimarySubtag);
+        }
+        for subtag in subtags {
+            if !(1..=8).contains(&subtag.len())
+                || !subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+            {
+                return Err(LangError::InvalidSubtag);
+            }
+        }
+        Ok(Self(code.to_ascii_lowercase()))
+    }
+
+    /// The primary language subtag, e.g. `zh` for `zh-hans-cn`.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::Lang;
+    /// assert_eq!(Lang("zh-hans-cn".to_string()).primary(), "zh");
+    /// assert_eq!(Lang("en".to_string()).primary(), "en");
+    /// ```
+    #[must_use]
+    pub fn primary(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// The script subtag, if there is one. This is the four-letter subtag that comes after the
+    /// primary language (and any extended language subtags).
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::Lang;
+    /// assert_eq!(Lang("zh-hans-cn".to_string()).script(), Some("hans"));
+    /// assert_eq!(Lang("en-gb".to_string()).script(), None);
+    /// ```
+    #[must_use]
+    pub fn script(&self) -> Option<&str> {
+        self.0
+            .split('-')
+            .skip(1)
+            .find(|subtag| !is_extlang(subtag))
+            .filter(|subtag| is_script(subtag))
+    }
+
+    /// The region subtag, if there is one. This is either a two-letter country code or a
+    /// three-digit UN M.49 area code.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::Lang;
+    /// assert_eq!(Lang("zh-hans-cn".to_string()).region(), Some("cn"));
+    /// assert_eq!(Lang("es-419".to_string()).region(), Some("419"));
+    /// assert_eq!(Lang("be-tarask".to_string()).region(), None);
+    /// ```
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.0
+            .split('-')
+            .skip(1)
+            .find(|subtag| !is_extlang(subtag) && !is_script(subtag))
+            .filter(|subtag| is_region(subtag))
+    }
+
+    /// Whether this language matches `other`, for the purposes of language fallback. A language
+    /// matches itself and any more specific variant of itself, so `en` matches `en-gb`, and `zh`
+    /// matches `zh-hans-cn`. The `mul` language (for text that is the same in multiple languages)
+    /// matches every language.
+    ///
+    /// This is directional: a more specific variant doesn't match a less specific one, so `en-gb`
+    /// doesn't match `en`, and nothing other than `mul` matches `mul`.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::Lang;
+    /// let en = Lang("en".to_string());
+    /// let en_gb = Lang("en-gb".to_string());
+    /// assert!(en.matches(&en_gb));
+    /// assert!(!en_gb.matches(&en));
+    /// assert!(!en.matches(&Lang("eo".to_string())));
+    /// assert!(Lang("mul".to_string()).matches(&en_gb));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, other: &Lang) -> bool {
+        if self.0 == "mul" {
+            return true;
+        }
+        let mut other_subtags = other.0.split('-');
+        self.0
+            .split('-')
+            .all(|subtag| other_subtags.next() == Some(subtag))
+    }
+}
+
+/// An error parsing a language code with [`Lang::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LangError {
+    /// The code was empty.
+    Empty,
+    /// The primary language subtag wasn't 2 to 8 letters.
+    InvalidPrimarySubtag,
+    /// A subtag other than the primary language subtag wasn't 1 to 8 letters or digits.
+    InvalidSubtag,
+}
+
+impl fmt::Display for LangError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LangError::Empty => write!(f, "language code is empty"),
+            LangError::InvalidPrimarySubtag => write!(f, "invalid primary language subtag"),
+            LangError::InvalidSubtag => write!(f, "invalid language subtag"),
+        }
+    }
+}
+
+impl std::error::Error for LangError {}
+
+fn is_extlang(subtag: &str) -> bool {
+    subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_script(subtag: &str) -> bool {
+    subtag.len() == 4 && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_region(subtag: &str) -> bool {
+    (subtag.len() == 2 && subtag.bytes().all(|b| b.is_ascii_alphabetic()))
+        || (subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Text that is in a certain language.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Text {
@@ -12,3 +205,42 @@ pub struct Text {
     /// The language of the text.
     pub lang: Lang,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        for code in [
+            "en",
+            "mul",
+            "zxx",
+            "zh-hans",
+            "zh-hans-cn",
+            "be-tarask",
+            "es-419",
+            "simple",
+            "zh-min-nan",
+            "de-1901",
+            "en-x-q123",
+        ] {
+            assert_eq!(Lang::parse(code), Ok(Lang(code.to_string())));
+        }
+        assert_eq!(Lang::parse("sr-Latn"), Ok(Lang("sr-latn".to_string())));
+        assert_eq!(Lang::parse(""), Err(LangError::Empty));
+        assert_eq!(Lang::parse("e"), Err(LangError::InvalidPrimarySubtag));
+        assert_eq!(
+            Lang::parse("toolonglang"),
+            Err(LangError::InvalidPrimarySubtag)
+        );
+        assert_eq!(Lang::parse("-en"), Err(LangError::InvalidPrimarySubtag));
+        assert_eq!(Lang::parse("en-"), Err(LangError::InvalidSubtag));
+        assert_eq!(Lang::parse("en--gb"), Err(LangError::InvalidSubtag));
+        assert_eq!(Lang::parse("en gb"), Err(LangError::InvalidPrimarySubtag));
+        assert_eq!(
+            Lang::parse("en-toolongsubtag"),
+            Err(LangError::InvalidSubtag)
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Points in time as Wikibase stores them.
+
+use std::{convert::TryFrom, fmt};
+
+use crate::entity::EntityError;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A point in time, stored the same way as in Wikibase: a signed year and the parts of the date
+/// and time that are known. Unlike a Chrono `DateTime`, this can represent any year Wikibase can,
+/// such as the formation of the Earth (`-4540000000`).
+///
+/// Times are ordered chronologically. When one time is less precise than another, such as `1952`
+/// and `1952-03-11`, the less precise one comes first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct WbTime {
+    /// The year. Negative years are BCE, and there's no year zero.
+    pub year: i64,
+    /// The month, from 1 to 12, if known.
+    pub month: Option<u8>,
+    /// The day of the month, if known.
+    pub day: Option<u8>,
+    /// The hour, if known.
+    pub hour: Option<u8>,
+    /// The minute, if known.
+    pub minute: Option<u8>,
+    /// The second, if known.
+    pub second: Option<u8>,
+    /// The precision of the time, as described in
+    /// [`ClaimValueData::DateTime`](crate::ClaimValueData::DateTime).
+    pub precision: u8,
+}
+
+impl WbTime {
+    /// Parse a Wikibase time string, such as `+1952-03-11T00:00:00Z`. Months and days of `00`
+    /// mean the month or day isn't known, as do times at midnight with a precision of a day or
+    /// less.
+    ///
+    /// ## Example
+    /// ```
+    /// use wikidata::WbTime;
+    /// let time = WbTime::parse("-4540000000-00-00T00:00:00Z", 3).unwrap();
+    /// assert_eq!(time.year, -4_540_000_000);
+    /// assert_eq!(time.month, None);
+    /// assert_eq!(time.to_chrono(), None);
+    /// ```
+    ///
+    /// # Errors
+    /// If the string isn't a Wikibase time string, an `EntityError` will be returned.
+    pub fn parse(time: &str, precision: u8) -> Result<Self, EntityError> {
+        let is_ce = match time.chars().next() {
+            Some('+') => true,
+            Some('-') => false,
+            Some(_) => return Err(EntityError::NoDateYear),
+            None => return Err(EntityError::TimeEmpty),
+        };
+        let (date, clock) = match time[1..].split_once('T') {
+            Some((date, clock)) => (date, Some(clock)),
+            None => (&time[1..], None),
+        };
+        let mut date_parts = date.split('-');
+        let year: i64 = date_parts
+            .next()
+            .and_then(|year| year.parse().ok())
+            .ok_or(EntityError::NoDateYear)?;
+        let part = |part: Option<&str>| match part.map(str::parse) {
+            Some(Ok(0)) | None => Ok(None),
+            Some(Ok(x)) => Ok(Some(x)),
+            Some(Err(_)) => Err(EntityError::FloatParse),
+        };
+        let month = part(date_parts.next())?;
+        let day = part(date_parts.next())?;
+
+        let (mut hour, mut minute, mut second) = (None, None, None);
+        if let Some(clock) = clock {
+            // the trailing Z is sometimes missing, and fractional seconds are truncated
+            let mut clock_parts = clock.trim_end_matches('Z').split(':');
+            let parse = |part: Option<&str>, err| {
+                part.ok_or(err)?
+                    .split('.')
+                    .next()
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .ok_or(EntityError::FloatParse)
+            };
+            let parsed = (
+                parse(clock_parts.next(), EntityError::MissingHour)?,
+                parse(clock_parts.next(), EntityError::MissingMinute)?,
+                parse(clock_parts.next(), EntityError::MissingSecond)?,
+            );
+            // midnight is how Wikibase writes "no time"
+            if precision > 11 || parsed != (0, 0, 0) {
+                hour = Some(parsed.0);
+                minute = Some(parsed.1);
+                second = Some(parsed.2);
+            }
+        }
+
+        Ok(Self {
+            year: if is_ce { year } else { -year },
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            precision,
+        })
+    }
+
+    /// Convert a Chrono `DateTime` to a `WbTime` with the given precision. Parts of the date more
+    /// precise than the precision are left out.
+    #[must_use]
+    pub fn from_chrono(date_time: &DateTime<Utc>, precision: u8) -> Self {
+        let clock = |x: u32| (precision > 11).then_some(x as u8);
+        Self {
+            year: i64::from(date_time.year()),
+            month: (precision >= 10).then(|| date_time.month() as u8),
+            day: (precision >= 11).then(|| date_time.day() as u8),
+            hour: clock(date_time.hour()),
+            minute: clock(date_time.minute()),
+            second: clock(date_time.second()),
+            precision,
+        }
+    }
+
+    /// Convert the time to a Chrono `DateTime`, with unknown parts of the date set to the start of
+    /// the year, month, or day. Returns `None` if Chrono can't represent the date.
+    ///
+    /// ## Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use wikidata::WbTime;
+    /// let time = WbTime::parse("+1952-03-00T00:00:00Z", 10).unwrap();
+    /// assert_eq!(time.to_chrono(), Some(Utc.with_ymd_and_hms(1952, 3, 1, 0, 0, 0).unwrap()));
+    /// ```
+    #[must_use]
+    pub fn to_chrono(&self) -> Option<DateTime<Utc>> {
+        Utc.with_ymd_and_hms(
+            i32::try_from(self.year).ok()?,
+            u32::from(self.month.unwrap_or(1)),
+            u32::from(self.day.unwrap_or(1)),
+            u32::from(self.hour.unwrap_or(0)),
+            u32::from(self.minute.unwrap_or(0)),
+            u32::from(self.second.unwrap_or(0)),
+        )
+        .single()
+    }
+}
+
+impl fmt::Display for WbTime {
+    /// Display the time as a Wikibase time string, such as `+1952-03-11T00:00:00Z`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            if self.year < 0 { '-' } else { '+' },
+            self.year.unsigned_abs(),
+            self.month.unwrap_or(0),
+            self.day.unwrap_or(0),
+            self.hour.unwrap_or(0),
+            self.minute.unwrap_or(0),
+            self.second.unwrap_or(0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        for (time, precision) in [
+            ("+1952-03-11T00:00:00Z", 11),
+            ("-0044-03-15T00:00:00Z", 11),
+            ("+1969-07-20T20:17:40Z", 14),
+            ("-13798000000-00-00T00:00:00Z", 3),
+            ("+2000000000-00-00T00:00:00Z", 0),
+        ] {
+            assert_eq!(WbTime::parse(time, precision).unwrap().to_string(), time);
+        }
+        let time = WbTime::parse("-13798000000-00-00T00:00:00Z", 3).unwrap();
+        assert_eq!(time.year, -13_798_000_000);
+        assert_eq!((time.month, time.day, time.hour), (None, None, None));
+        assert_eq!(WbTime::parse("", 9), Err(EntityError::TimeEmpty));
+        assert_eq!(WbTime::parse("1952", 9), Err(EntityError::NoDateYear));
+    }
+
+    #[test]
+    fn chrono_conversion() {
+        let date_time = Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap();
+        let time = WbTime::from_chrono(&date_time, 14);
+        assert_eq!(time.to_string(), "+1969-07-20T20:17:40Z");
+        assert_eq!(time.to_chrono(), Some(date_time));
+        assert_eq!(
+            WbTime::from_chrono(&date_time, 9).to_string(),
+            "+1969-00-00T00:00:00Z"
+        );
+        assert_eq!(
+            WbTime::parse("-4540000000-00-00T00:00:00Z", 3)
+                .unwrap()
+                .to_chrono(),
+            None
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let mut times: Vec<WbTime> = [
+            ("+1952-03-11T00:00:00Z", 11),
+            ("-13798000000-00-00T00:00:00Z", 3),
+            ("+1952-00-00T00:00:00Z", 9),
+            ("+1969-07-20T20:17:40Z", 14),
+            ("-0044-03-15T00:00:00Z", 11),
+            ("+1952-03-00T00:00:00Z", 10),
+        ]
+        .iter()
+        .map(|(time, precision)| WbTime::parse(time, *precision).unwrap())
+        .collect();
+        times.sort();
+        let times: Vec<String> = times.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            times,
+            [
+                "-13798000000-00-00T00:00:00Z",
+                "-0044-03-15T00:00:00Z",
+                "+1952-00-00T00:00:00Z",
+                "+1952-03-00T00:00:00Z",
+                "+1952-03-11T00:00:00Z",
+                "+1969-07-20T20:17:40Z",
+            ]
+        );
+    }
+}
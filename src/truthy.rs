@@ -0,0 +1,99 @@
+//! A compact, "truthy" view of an entity for serialization.
+
+use std::collections::BTreeMap;
+
+use crate::entity::{
+    best_rank_claims, ClaimValueData, Entity, EntityType, SiteName, SitelinkValue,
+};
+use crate::ids::{Pid, WikiId};
+use crate::text::Lang;
+use serde::{Serialize, Serializer};
+
+/// A view of an [`Entity`] that only serializes its truthy claims.
+///
+/// A claim is truthy if it is not deprecated and has the best rank out of all the claims for its
+/// property (so if any claim for a property is preferred, only the preferred claims are kept).
+/// Qualifiers, references, claim IDs, and ranks are left out. Everything else is serialized the
+/// same way [`Entity`] serializes it, except that `claims` is a list of `(Pid, ClaimValueData)`
+/// pairs.
+///
+/// This doesn't change how [`Entity`] itself is serialized.
+///
+/// ## Example
+/// ```
+/// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+/// # let q42 = wikidata::Entity::from_json(j).unwrap();
+/// let json = serde_json::to_value(wikidata::TruthyView(&q42)).unwrap();
+/// assert!(json["claims"][0][1].is_object());
+/// assert!(json["claims"][0][1].get("references").is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TruthyView<'a>(pub &'a Entity);
+
+#[derive(Serialize)]
+struct TruthyEntity<'a> {
+    id: WikiId,
+    claims: Vec<(Pid, &'a ClaimValueData)>,
+    entity_type: EntityType,
+    descriptions: &'a BTreeMap<Lang, String>,
+    labels: &'a BTreeMap<Lang, String>,
+    aliases: &'a BTreeMap<Lang, Vec<String>>,
+    sitelinks: &'a BTreeMap<SiteName, SitelinkValue>,
+}
+
+impl Serialize for TruthyView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entity = self.0;
+        let claims = best_rank_claims(entity.claims.iter())
+            .map(|(pid, claim)| (*pid, &claim.data))
+            .collect();
+        TruthyEntity {
+            id: entity.id,
+            claims,
+            entity_type: entity.entity_type,
+            descriptions: &entity.descriptions,
+            labels: &entity.labels,
+            aliases: &entity.aliases,
+            sitelinks: &entity.sitelinks,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::{ClaimValue, Rank};
+    use crate::ids::Qid;
+
+    #[test]
+    fn only_best_rank() {
+        let claim = |qid, rank| ClaimValue {
+            data: ClaimValueData::Item(Qid(qid)),
+            rank,
+            ..ClaimValue::default()
+        };
+        let entity = Entity {
+            id: WikiId::EntityId(Qid(1)),
+            claims: vec![
+                (Pid(31), claim(5, Rank::Normal)),
+                (Pid(31), claim(6, Rank::Preferred)),
+                (Pid(31), claim(7, Rank::Deprecated)),
+                (Pid(17), claim(8, Rank::Normal)),
+                (Pid(17), claim(9, Rank::Normal)),
+                (Pid(18), claim(10, Rank::Deprecated)),
+            ],
+            entity_type: EntityType::Entity,
+            descriptions: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            sitelinks: BTreeMap::new(),
+            lexeme: None,
+        };
+        let json = serde_json::to_value(TruthyView(&entity)).unwrap();
+        assert_eq!(
+            json["claims"],
+            serde_json::json!([[31, {"Item": 6}], [17, {"Item": 8}], [17, {"Item": 9}]])
+        );
+    }
+}
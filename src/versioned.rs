@@ -0,0 +1,98 @@
+//! Opt-in versioning for the crate-specific serialization format.
+
+use std::fmt;
+
+use crate::entity::Entity;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The version of the crate-specific serialization format produced by the [`Serialize`] impls in
+/// this crate. This is increased whenever a change to the crate makes previously serialized data
+/// deserialize differently, or not at all.
+pub const FORMAT_VERSION: u32 = 5;
+
+/// An [`Entity`] that is serialized along with the [`FORMAT_VERSION`] it was serialized with, as
+/// `{"format_version": 5, "entity": {...}}`. Deserializing fails if the stored format version is
+/// missing or different from the current one.
+///
+/// This is opt-in: serializing an [`Entity`] directly doesn't include the format version.
+///
+/// ## Example
+/// ```
+/// # let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+/// # let q42 = wikidata::Entity::from_json(j).unwrap();
+/// use wikidata::VersionedEntity;
+/// let json = serde_json::to_string(&VersionedEntity(q42.clone())).unwrap();
+/// let VersionedEntity(entity) = serde_json::from_str(&json).unwrap();
+/// assert_eq!(entity, q42);
+///
+/// let old = json.replacen("\"format_version\":5", "\"format_version\":4", 1);
+/// assert!(serde_json::from_str::<VersionedEntity>(&old).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionedEntity(pub Entity);
+
+#[derive(Serialize)]
+struct VersionedRef<'a> {
+    format_version: u32,
+    entity: &'a Entity,
+}
+
+impl Serialize for VersionedEntity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VersionedRef {
+            format_version: FORMAT_VERSION,
+            entity: &self.0,
+        }
+        .serialize(serializer)
+    }
+}
+
+fn check_version<E: de::Error>(version: u32) -> Result<(), E> {
+    if version == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(E::custom(format_args!(
+            "serialized with format version {version}, but the current format version is {FORMAT_VERSION}"
+        )))
+    }
+}
+
+struct VersionedVisitor;
+
+impl<'de> de::Visitor<'de> for VersionedVisitor {
+    type Value = VersionedEntity;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an object with a format_version and an entity")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut version = None;
+        let mut entity = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match &key[..] {
+                "format_version" => {
+                    // check the version as soon as possible, so an incompatible entity gives a
+                    // version error instead of a confusing error about the entity
+                    let v = map.next_value()?;
+                    check_version(v)?;
+                    version = Some(v);
+                }
+                "entity" => entity = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+        check_version(version.ok_or_else(|| de::Error::missing_field("format_version"))?)?;
+        entity
+            .map(VersionedEntity)
+            .ok_or_else(|| de::Error::missing_field("entity"))
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedEntity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(VersionedVisitor)
+    }
+}
@@ -18,6 +18,30 @@ fn id_snak() {
     assert_eq!(data, ClaimValueData::Item(Qid(323)));
 }
 
+#[test]
+fn numeric_id_snak() {
+    let snak: serde_json::Value =
+        serde_json::from_str(include_str!("../items/numeric-id-snak.json")).unwrap();
+    let data = ClaimValueData::parse_snak(snak).unwrap();
+    assert_eq!(data, ClaimValueData::Item(Qid(5)));
+}
+
+#[test]
+fn property_snak() {
+    let snak: serde_json::Value =
+        serde_json::from_str(include_str!("../items/property-snak.json")).unwrap();
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(data, ClaimValueData::Property(Pid(361)));
+
+    let mut numeric_only = snak;
+    numeric_only["datavalue"]["value"]
+        .as_object_mut()
+        .unwrap()
+        .remove("id");
+    let data = ClaimValueData::parse_snak(numeric_only).unwrap();
+    assert_eq!(data, ClaimValueData::Property(Pid(361)));
+}
+
 #[test]
 fn commons_snak() {
     let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
@@ -42,11 +66,202 @@ fn quantity_snak() {
             amount: 1.96,
             lower_bound: None,
             upper_bound: None,
-            unit: Some(Qid(11573))
+            unit: Some(EntityOrIri::Entity(Qid(11573)))
         }
     );
 }
 
+#[test]
+fn format_quantity() {
+    let quantity = |amount, lower_bound, upper_bound, unit: Option<Qid>| ClaimValueData::Quantity {
+        amount,
+        lower_bound,
+        upper_bound,
+        unit: unit.map(EntityOrIri::from),
+    };
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let snak = &j["entities"]["Q42"]["claims"]["P2048"][0]["mainsnak"];
+    let height = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(height.format_quantity().unwrap(), "1.96 m");
+    assert_eq!(
+        quantity(1.96, Some(1.955), Some(1.965), Some(consts::METRE))
+            .format_quantity()
+            .unwrap(),
+        "1.96±0.005 m"
+    );
+    // asymmetric bounds aren't shown
+    assert_eq!(
+        quantity(1.96, Some(1.95), Some(1.98), Some(consts::METRE))
+            .format_quantity()
+            .unwrap(),
+        "1.96 m"
+    );
+    assert_eq!(
+        quantity(90.0, None, None, Some(consts::DEGREE))
+            .format_quantity()
+            .unwrap(),
+        "90°"
+    );
+    assert_eq!(
+        quantity(8_857_716.0, None, None, None)
+            .format_quantity()
+            .unwrap(),
+        "8857716"
+    );
+    assert_eq!(
+        quantity(3.0, Some(3.0), Some(3.0), Some(Qid(1)))
+            .format_quantity()
+            .unwrap(),
+        "3"
+    );
+    assert_eq!(ClaimValueData::Item(Qid(5)).format_quantity(), None);
+}
+
+#[test]
+fn quantity_si() {
+    let mass = |amount, unit: Qid| ClaimValueData::Quantity {
+        amount,
+        lower_bound: None,
+        upper_bound: None,
+        unit: Some(unit.into()),
+    };
+    assert_eq!(mass(5.0, consts::TONNE).quantity_si(), Some((5e3, "kg")));
+    let total: f64 = [
+        mass(500.0, consts::GRAM),
+        mass(2.0, consts::KILOGRAM),
+        mass(1.5, consts::TONNE),
+    ]
+    .iter()
+    .map(|data| data.quantity_si().unwrap().0)
+    .sum();
+    assert_eq!(total, 1_502.5);
+}
+
+#[test]
+fn population_snak() {
+    // a "+"-prefixed amount, no bounds, and a unit of "1"
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+    let snak = &j["entities"]["Q45"]["claims"]["P1082"][0]["mainsnak"];
+    println!("{:?}", snak);
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::Quantity {
+            amount: 8_857_716.0,
+            lower_bound: None,
+            upper_bound: None,
+            unit: None
+        }
+    );
+}
+
+#[test]
+fn string_coordinates_snak() {
+    let snak: serde_json::Value =
+        serde_json::from_str(include_str!("../items/string-coordinates.json")).unwrap();
+    let data = ClaimValueData::parse_snak(snak).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::GlobeCoordinate {
+            lat: 51.507_222_222_222,
+            lon: -0.1275,
+            precision: Some(2.777_777_777_777_8e-6),
+            globe: consts::EARTH.into(),
+        }
+    );
+}
+
+#[test]
+fn coordinates_without_precision() {
+    let snak = serde_json::json!({
+        "snaktype": "value",
+        "property": "P625",
+        "datatype": "globe-coordinate",
+        "datavalue": {
+            "type": "globecoordinate",
+            "value": {
+                "latitude": 1.5,
+                "longitude": 2.5,
+                "precision": null,
+                "globe": "http://www.wikidata.org/entity/Q2"
+            }
+        }
+    });
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::GlobeCoordinate {
+            lat: 1.5,
+            lon: 2.5,
+            precision: None,
+            globe: consts::EARTH.into(),
+        }
+    );
+    assert_eq!(
+        data.to_snak_json(Datatype::GlobeCoordinate)["datavalue"]["value"]["precision"],
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn foreign_iri_snaks() {
+    // a coordinate on a globe and a quantity in a unit from outside of Wikidata
+    let snak = serde_json::json!({
+        "snaktype": "value",
+        "property": "P625",
+        "datatype": "globe-coordinate",
+        "datavalue": {
+            "type": "globecoordinate",
+            "value": {
+                "latitude": 1.5,
+                "longitude": 2.5,
+                "precision": 0.1,
+                "globe": "https://wikibase.example.org/entity/Q3"
+            }
+        }
+    });
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::GlobeCoordinate {
+            lat: 1.5,
+            lon: 2.5,
+            precision: Some(0.1),
+            globe: EntityOrIri::Iri("https://wikibase.example.org/entity/Q3".to_string()),
+        }
+    );
+    assert_eq!(
+        data.to_snak_json(Datatype::GlobeCoordinate)["datavalue"]["value"]["globe"],
+        snak["datavalue"]["value"]["globe"]
+    );
+
+    let snak = serde_json::json!({
+        "snaktype": "value",
+        "property": "P2048",
+        "datatype": "quantity",
+        "datavalue": {
+            "type": "quantity",
+            "value": {
+                "amount": "+3",
+                "unit": "http://qudt.org/vocab/unit/FT"
+            }
+        }
+    });
+    let data = ClaimValueData::parse_snak(snak).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::Quantity {
+            amount: 3.0,
+            lower_bound: None,
+            upper_bound: None,
+            unit: Some(EntityOrIri::Iri(
+                "http://qudt.org/vocab/unit/FT".to_string()
+            )),
+        }
+    );
+    assert_eq!(data.quantity_si(), None);
+}
+
 #[test]
 fn external_id_snak() {
     let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
@@ -70,8 +285,8 @@ fn coordinates_snak() {
         ClaimValueData::GlobeCoordinate {
             lat: 27.988055555556,
             lon: 86.925277777778,
-            precision: 0.00027777777777778,
-            globe: Qid(2)
+            precision: Some(0.00027777777777778),
+            globe: EntityOrIri::Entity(Qid(2))
         }
     );
 }
@@ -99,7 +314,56 @@ fn date_snak() {
     let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
     assert_eq!(
         &format!("{:?}", data),
-        "DateTime { date_time: 1952-03-11T00:00:00Z, precision: 11 }",
+        "DateTime { date_time: 1952-03-11T00:00:00Z, precision: 11, calendar: Qid(1985727) }",
+    );
+}
+
+#[test]
+fn julian_date_snak() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
+    let snak = &j["entities"]["Q45"]["claims"]["P571"][0]["mainsnak"];
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        &format!("{:?}", data),
+        "DateTime { date_time: 1143-10-05T00:00:00Z, precision: 11, calendar: Qid(1985786) }",
+    );
+
+    let mut snak = snak.clone();
+    snak["datavalue"]["value"]
+        .as_object_mut()
+        .unwrap()
+        .remove("calendarmodel");
+    match ClaimValueData::parse_snak(snak).unwrap() {
+        ClaimValueData::DateTime { calendar, .. } => {
+            assert_eq!(calendar, consts::PROLEPTIC_GREGORIAN_CALENDAR);
+        }
+        data => panic!("expected a date, got {:?}", data),
+    }
+}
+
+#[test]
+fn out_of_range_time_snak() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q1.json")).unwrap();
+    let snak = &j["entities"]["Q1"]["claims"]["P580"][0]["mainsnak"];
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        data,
+        ClaimValueData::OutOfRangeTime {
+            time: WbTime {
+                year: -13_798_000_000,
+                month: None,
+                day: None,
+                hour: None,
+                minute: None,
+                second: None,
+                precision: 3,
+            },
+            calendar: consts::PROLEPTIC_GREGORIAN_CALENDAR,
+        }
+    );
+    assert_eq!(
+        data.to_snak_json(Datatype::Time)["datavalue"]["value"],
+        snak["datavalue"]["value"]
     );
 }
 
@@ -111,3 +375,89 @@ fn lexeme_snak() {
     let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
     assert_eq!(data, ClaimValueData::Lexeme(Lid(361)));
 }
+
+#[test]
+fn deprecated_time_precisions() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/deprecated-time-precisions.json")).unwrap();
+    let snak = &j["claims"]["P585"][0]["mainsnak"];
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        &format!("{:?}", data),
+        "DateTime { date_time: 1969-07-20T20:00:00Z, precision: 12, calendar: Qid(1985727) }",
+    );
+
+    let snak = &j["claims"]["P585"][1]["mainsnak"];
+    let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+    assert_eq!(
+        &format!("{:?}", data),
+        "DateTime { date_time: 1969-07-20T20:17:40Z, precision: 14, calendar: Qid(1985727) }",
+    );
+
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.claims.len(), 2);
+}
+
+#[test]
+fn time_zone_offset() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/deprecated-time-precisions.json")).unwrap();
+    let mut snak = j["claims"]["P585"][1]["mainsnak"].clone();
+    snak["datavalue"]["value"]["timezone"] = serde_json::json!(-300);
+    let data = ClaimValueData::parse_snak(snak).unwrap();
+    assert_eq!(
+        &format!("{:?}", data),
+        "DateTime { date_time: 1969-07-21T01:17:40Z, precision: 14, calendar: Qid(1985727) }",
+    );
+
+    // dates without a time of day stay on the same day
+    let mut snak = j["claims"]["P585"][0]["mainsnak"].clone();
+    snak["datavalue"]["value"]["precision"] = serde_json::json!(11);
+    snak["datavalue"]["value"]["timezone"] = serde_json::json!(-300);
+    let data = ClaimValueData::parse_snak(snak).unwrap();
+    assert_eq!(
+        &format!("{:?}", data),
+        "DateTime { date_time: 1969-07-20T20:00:00Z, precision: 11, calendar: Qid(1985727) }",
+    );
+
+    let mut snak = j["claims"]["P585"][1]["mainsnak"].clone();
+    snak["datavalue"]["value"]["timezone"] = serde_json::json!(1440);
+    assert_eq!(
+        ClaimValueData::parse_snak(snak),
+        Err(EntityError::InvalidTimezone)
+    );
+
+    // times that the offset moves out of Chrono's range are kept as written
+    let mut snak = j["claims"]["P585"][1]["mainsnak"].clone();
+    snak["datavalue"]["value"]["time"] = serde_json::json!("+262142-12-31T23:00:00Z");
+    snak["datavalue"]["value"]["timezone"] = serde_json::json!(-300);
+    match ClaimValueData::parse_snak(snak).unwrap() {
+        ClaimValueData::OutOfRangeTime { time, .. } => {
+            assert_eq!(time.to_string(), "+262142-12-31T23:00:00Z");
+        }
+        data => panic!("expected an out of range time, got {:?}", data),
+    }
+}
+
+#[test]
+fn snak_to_json() {
+    let q42: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let q513: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    let q1: serde_json::Value = serde_json::from_str(include_str!("../items/Q1.json")).unwrap();
+    let snaks = [
+        &q42["entities"]["Q42"]["claims"]["P18"][0]["mainsnak"],
+        &q42["entities"]["Q42"]["claims"]["P213"][0]["mainsnak"],
+        &q42["entities"]["Q42"]["claims"]["P569"][0]["mainsnak"],
+        &q42["entities"]["Q42"]["claims"]["P1477"][0]["mainsnak"],
+        &q42["entities"]["Q42"]["claims"]["P2048"][0]["mainsnak"],
+        &q513["entities"]["Q513"]["claims"]["P625"][0]["mainsnak"],
+        &q1["entities"]["Q1"]["claims"]["P793"][0]["mainsnak"],
+    ];
+    for snak in snaks {
+        let mut snak = snak.clone();
+        snak.as_object_mut().unwrap().remove("property");
+        let datatype: Datatype = snak["datatype"].as_str().unwrap().parse().unwrap();
+        let data = ClaimValueData::parse_snak(snak.clone()).unwrap();
+        assert_eq!(data.to_snak_json(datatype), snak);
+    }
+}
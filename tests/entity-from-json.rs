@@ -14,6 +14,15 @@ fn douglas_adams() {
     assert_eq!(e.id, WikiId::EntityId(Qid(42)));
 }
 
+#[test]
+fn claim_entity_ids() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    for (_, claim) in &e.claims {
+        assert_eq!(claim.entity_id(), Some(e.id));
+    }
+}
+
 #[test]
 fn universe() {
     let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q1.json")).unwrap();
@@ -37,3 +46,652 @@ fn portugal() {
     let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q45.json")).unwrap();
     Entity::from_json(j).unwrap();
 }
+
+#[test]
+fn plain_string_labels() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/wikibase-plain-strings.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.labels[&Lang("en".to_string())], "Foo");
+    assert_eq!(e.labels[&Lang("de".to_string())], "Föö");
+    assert_eq!(
+        e.descriptions[&Lang("en".to_string())],
+        "a minimal item from a non-Wikidata Wikibase"
+    );
+    assert_eq!(
+        e.aliases[&Lang("en".to_string())],
+        vec!["Bar".to_string(), "Baz".to_string()]
+    );
+}
+
+#[test]
+fn sitelink_without_title() {
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "claims": {},
+        "sitelinks": {
+            "enwiki": { "site": "enwiki", "badges": ["Q17437796"] }
+        }
+    });
+    assert_eq!(Entity::from_json(j), Err(EntityError::MissingSitelinkTitle));
+
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "claims": {},
+        "sitelinks": {
+            "enwiki": { "site": "enwiki", "title": "", "badges": [] }
+        }
+    });
+    assert_eq!(Entity::from_json(j), Err(EntityError::MissingSitelinkTitle));
+
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "claims": {},
+        "sitelinks": {
+            "enwiki": { "site": "enwiki", "title": "Foo" }
+        }
+    });
+    let e = Entity::from_json(j).unwrap();
+    assert!(e.sitelinks[&SiteName("enwiki".to_string())]
+        .badges
+        .is_empty());
+}
+
+#[test]
+fn property_mismatch() {
+    let mut j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/Q106975887.json")).unwrap();
+    j["entities"]["Q106975887"]["claims"]["P31"][0]["mainsnak"]["property"] =
+        serde_json::json!("P279");
+    let options = ParseOptions {
+        check_property_mismatch: true,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        Entity::from_json_with_options(j.clone(), &options),
+        Err(EntityError::PropertyMismatch {
+            outer: Pid(31),
+            inner: Pid(279)
+        })
+    );
+    assert!(Entity::from_json(j).is_ok());
+}
+
+#[test]
+fn error_display() {
+    let err = EntityError::PropertyMismatch {
+        outer: Pid(31),
+        inner: Pid(279),
+    };
+    assert_eq!(
+        err.to_string(),
+        "claim listed under P31 has a mainsnak for P279"
+    );
+    let err: Box<dyn std::error::Error> = Box::new(EntityError::NoClaims);
+    assert_eq!(err.to_string(), "entity has no claims field");
+
+    let err = DumpError {
+        line: 3,
+        id: Some("Q3".to_string()),
+        source: EntityError::InvalidJson,
+    };
+    assert_eq!(err.to_string(), "line 3 (Q3): invalid JSON");
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn claim_limit() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let options = ParseOptions {
+        max_claims: Some(10),
+        ..ParseOptions::default()
+    };
+    let e = Entity::from_json_with_options(j.clone(), &options).unwrap();
+    assert_eq!(e.claims.len(), 10);
+
+    let options = ParseOptions {
+        max_claims: Some(10),
+        error_on_claim_limit: true,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        Entity::from_json_with_options(j, &options),
+        Err(EntityError::ClaimLimitExceeded)
+    );
+}
+
+#[test]
+fn ndjson() {
+    let q42: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let q513: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    let ndjson = format!(
+        "{}\n\n{}\nnot json\n{{\"entity\": \"Q123\", \"redirect\": \"Q456\"}}\n",
+        q42["entities"]["Q42"], q513["entities"]["Q513"]
+    );
+    let entities: Vec<_> = Entity::parse_ndjson(&ndjson).collect();
+    assert_eq!(entities.len(), 4);
+    assert_eq!(entities[0].as_ref().unwrap().id, WikiId::EntityId(Qid(42)));
+    assert_eq!(entities[1].as_ref().unwrap().id, WikiId::EntityId(Qid(513)));
+    assert_eq!(
+        entities[2],
+        Err(DumpError {
+            line: 4,
+            id: None,
+            source: EntityError::InvalidJson
+        })
+    );
+    assert_eq!(
+        entities[3],
+        Err(DumpError {
+            line: 5,
+            id: Some("Q123".to_string()),
+            source: EntityError::Redirect {
+                from: WikiId::EntityId(Qid(123)),
+                to: WikiId::EntityId(Qid(456)),
+            },
+        })
+    );
+}
+
+#[test]
+fn numeric_id() {
+    let j = serde_json::json!({ "type": "item", "id": 42, "claims": {} });
+    assert_eq!(Entity::from_json(j).unwrap().id, WikiId::EntityId(Qid(42)));
+
+    let j = serde_json::json!({ "type": "property", "id": 31, "claims": {} });
+    assert_eq!(
+        Entity::from_json(j).unwrap().id,
+        WikiId::PropertyId(Pid(31))
+    );
+
+    let j = serde_json::json!({ "type": "item", "id": [42], "claims": {} });
+    assert_eq!(Entity::from_json(j), Err(EntityError::NonStringId));
+}
+
+#[test]
+fn dump_slice() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let line = format!("{},\n", j["entities"]["Q42"]);
+    let e = Entity::from_dump_slice(line.as_bytes()).unwrap();
+    assert_eq!(e, Entity::from_json(j).unwrap());
+    assert_eq!(
+        Entity::from_dump_slice(b"[\n"),
+        Err(EntityError::InvalidJson)
+    );
+}
+
+#[test]
+fn blank_labels() {
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "labels": {
+            "en": { "language": "en", "value": "" },
+            "de": { "language": "de", "value": "  " },
+            "mul": { "language": "mul", "value": "Foo" }
+        },
+        "descriptions": { "en": { "language": "en", "value": "" } },
+        "aliases": { "en": [{ "language": "en", "value": " " }] },
+        "claims": {}
+    });
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.labels.len(), 1);
+    assert_eq!(e.labels[&Lang("mul".to_string())], "Foo");
+    assert!(e.descriptions.is_empty());
+    assert!(e.aliases.is_empty());
+}
+
+#[test]
+fn external_id_urls_escaping() {
+    let e = EntityBuilder::new(WikiId::EntityId(Qid(42)))
+        .add_claim(
+            consts::VIAF_ID,
+            ClaimValueData::ExternalID("a b#c?d&é/1".to_string()),
+        )
+        .build();
+    assert_eq!(
+        e.external_id_urls(),
+        vec![(
+            consts::VIAF_ID,
+            "https://viaf.org/viaf/a%20b%23c%3Fd%26%C3%A9/1".to_string()
+        )]
+    );
+}
+
+#[test]
+fn simple_json_names() {
+    let e = EntityBuilder::new(WikiId::EntityId(Qid(42)))
+        .add_claim(consts::INSTANCE_OF, ClaimValueData::Item(consts::HUMAN))
+        .add_claim(
+            consts::PSUEDONYM,
+            ClaimValueData::MonolingualText(Text {
+                text: "DNA".to_string(),
+                lang: Lang::en(),
+            }),
+        )
+        .add_claim(consts::FB_ID, ClaimValueData::ExternalID("dna".to_string()))
+        .add_claim(
+            consts::YT_CHANNEL_ID,
+            ClaimValueData::ExternalID("UC1".to_string()),
+        )
+        .build();
+    let json = e.to_simple_json(&Lang::en());
+    let mut keys: Vec<_> = json["claims"].as_object().unwrap().keys().collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "facebook id",
+            "instance of",
+            "pseudonym",
+            "youtube channel id"
+        ]
+    );
+    assert_eq!(json["claims"]["instance of"][0], "human");
+}
+
+#[test]
+fn wikipedia_url() {
+    let e = EntityBuilder::new(WikiId::EntityId(Qid(42)))
+        .sitelink("enwiki", "Douglas Adams")
+        .sitelink("commonswiki", "Category:Douglas Adams")
+        .sitelink("specieswiki", "Douglas Adams")
+        .sitelink("metawiki", "Douglas Adams")
+        .sitelink("wikidatawiki", "Douglas Adams")
+        .sitelink("mediawikiwiki", "Douglas Adams")
+        .sitelink("sourceswiki", "Author:Douglas Adams")
+        .build();
+    assert_eq!(
+        e.wikipedia_url("en").as_deref(),
+        Some("https://en.wikipedia.org/wiki/Douglas_Adams")
+    );
+    for site in [
+        "commons",
+        "species",
+        "meta",
+        "wikidata",
+        "mediawiki",
+        "sources",
+    ] {
+        assert_eq!(e.wikipedia_url(site), None, "{site}");
+    }
+}
+
+#[test]
+fn sitelinks_only() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/sitelinks-only.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.id, WikiId::EntityId(Qid(42)));
+    assert!(e.claims.is_empty());
+    assert!(e.labels.is_empty());
+    assert!(e.descriptions.is_empty());
+    assert!(e.aliases.is_empty());
+    assert_eq!(e.sitelinks.len(), 4);
+    assert_eq!(e.sitelink_title("enwiki"), Some("Douglas Adams"));
+    assert_eq!(
+        e.sitelink("enwiki").unwrap().url.as_deref(),
+        Some("https://en.wikipedia.org/wiki/Douglas_Adams")
+    );
+    assert_eq!(e.sitelink("dewiki").unwrap().url, None);
+    assert_eq!(
+        e.sitelinks[&SiteName("frwiki".to_string())].badges,
+        vec![Qid(17_437_796)]
+    );
+}
+
+#[test]
+fn multi_valued_reference() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/multi-valued-reference.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    let references = &e.claims[0].1.references;
+    assert_eq!(references.len(), 1);
+    assert_eq!(
+        references[0].claims,
+        vec![
+            (
+                consts::REFERENCE_URL,
+                ClaimValueData::Url("https://example.org/first".to_string())
+            ),
+            (
+                consts::REFERENCE_URL,
+                ClaimValueData::Url("https://example.org/second".to_string())
+            ),
+            (consts::STATED_IN, ClaimValueData::Item(Qid(36578))),
+        ]
+    );
+    assert_eq!(references[0].pid_claims(consts::REFERENCE_URL).count(), 2);
+}
+
+#[test]
+fn wikibase_json_roundtrip() {
+    for json in [
+        include_str!("../items/Q1.json"),
+        include_str!("../items/Q42.json"),
+        include_str!("../items/Q45.json"),
+        include_str!("../items/Q513.json"),
+        include_str!("../items/Q31928.json"),
+        include_str!("../items/Q106975887.json"),
+        include_str!("../items/lexeme-cat.json"),
+    ] {
+        let j: serde_json::Value = serde_json::from_str(json).unwrap();
+        let e = Entity::from_json(j).unwrap();
+        assert_eq!(Entity::from_json(e.to_wikibase_json()).unwrap(), e);
+    }
+}
+
+#[test]
+fn wikibase_json_multilingual() {
+    let j: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let q42 = Entity::from_json(j).unwrap();
+    let names = q42.collect_multilingual(Pid(1477));
+    let mut e = q42.clone();
+    e.add_claim(
+        Pid(1477),
+        ClaimValue {
+            data: names.clone(),
+            ..ClaimValue::default()
+        },
+    );
+    let claim = &mut e.claims[0].1;
+    claim.qualifiers.push((Pid(1477), names.clone()));
+    claim.references.push(ReferenceGroup {
+        claims: vec![(Pid(1477), names)],
+        hash: "abc".to_string(),
+    });
+    let json = e.to_wikibase_json();
+    assert_eq!(json["claims"], q42.to_wikibase_json()["claims"]);
+    assert_eq!(Entity::from_json(json).unwrap(), q42);
+}
+
+#[test]
+fn lexeme() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.id, WikiId::LexemeId(Lid(7)));
+    assert_eq!(e.entity_type, EntityType::Lexeme);
+    assert_eq!(e.claims.len(), 1);
+    let lexeme = e.lexeme.unwrap();
+    assert_eq!(lexeme.lemmas[&Lang::en()], "cat");
+    assert_eq!(lexeme.language, Qid(1860));
+    assert_eq!(lexeme.lexical_category, Qid(1084));
+
+    assert_eq!(lexeme.forms.len(), 2);
+    assert_eq!(lexeme.forms[0].id, Fid(Lid(7), 1));
+    assert_eq!(lexeme.forms[1].representations[&Lang::en()], "cats");
+    assert_eq!(lexeme.forms[1].grammatical_features, vec![Qid(146_786)]);
+    assert_eq!(
+        lexeme.forms[0].claims[0].1.data,
+        ClaimValueData::String("kæt".to_string())
+    );
+    assert!(lexeme.forms[1].claims.is_empty());
+    let singulars = lexeme.forms_with_features(&[consts::SINGULAR]);
+    assert_eq!(singulars, vec![&lexeme.forms[0]]);
+    assert_eq!(lexeme.forms_with_features(&[]).len(), 2);
+    assert!(lexeme
+        .forms_with_features(&[consts::SINGULAR, consts::PLURAL])
+        .is_empty());
+
+    assert_eq!(lexeme.senses.len(), 1);
+    assert_eq!(lexeme.senses[0].id, Sid(Lid(7), 1));
+    assert_eq!(lexeme.senses[0].glosses[&Lang::de()], "Hauskatze");
+    assert_eq!(
+        lexeme.sense_by_id(Sid(Lid(7), 1)).unwrap().gloss("en"),
+        Some("domesticated feline")
+    );
+    assert_eq!(
+        lexeme.senses[0].claims[0].1.data,
+        ClaimValueData::Item(Qid(146))
+    );
+
+    let mut sense = lexeme.senses[0].clone();
+    assert_eq!(
+        sense.gloss_with_fallback(&["fr", "en"]),
+        Some("domesticated feline")
+    );
+    sense.glosses.insert(Lang::fr(), String::new());
+    sense.glosses.insert(Lang::mul(), "Felis catus".to_string());
+    assert_eq!(
+        sense.gloss_with_fallback(&["fr", "es"]),
+        Some("Felis catus")
+    );
+    let mut form = lexeme.forms[0].clone();
+    form.representations.insert(Lang::mul(), "cat".to_string());
+    assert_eq!(form.representation_with_fallback(&["de"]), Some("cat"));
+
+    // lexemes without lexeme data (like from partial fetches) are still parsed
+    let partial = serde_json::json!({ "type": "lexeme", "id": "L7" });
+    assert_eq!(Entity::from_json(partial).unwrap().lexeme, None);
+    let no_language = serde_json::json!({ "type": "lexeme", "id": "L7", "lemmas": {} });
+    assert_eq!(
+        Entity::from_json(no_language),
+        Err(EntityError::NoLexemeLanguage)
+    );
+}
+
+#[test]
+fn lexeme_navigation() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    let lexeme = Entity::from_json(j).unwrap().lexeme.unwrap();
+    assert_eq!(lexeme.iter_forms().count(), 2);
+    assert_eq!(lexeme.iter_senses().count(), 1);
+
+    // resolve form and sense values, like ones from claims, within the same lexeme
+    let form_value = ClaimValueData::Form(Fid(Lid(7), 2));
+    let ClaimValueData::Form(fid) = form_value else {
+        panic!("expected a form")
+    };
+    let form = lexeme.form_by_id(fid).unwrap();
+    assert_eq!(form.representation("en"), Some("cats"));
+    assert!(form.has_features(&[consts::PLURAL]));
+    assert_eq!(lexeme.form_by_id(Fid(Lid(8), 2)), None);
+
+    let sense = lexeme.sense_by_id(Sid(Lid(7), 1)).unwrap();
+    assert_eq!(sense.gloss("de"), Some("Hauskatze"));
+    assert_eq!(lexeme.sense_by_id(Sid(Lid(7), 2)), None);
+}
+
+#[test]
+fn lexeme_claims() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/lexeme-cat.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    let mut referenced = e.clone();
+    let form_claim = &mut referenced.lexeme.as_mut().unwrap().forms[0].claims[0].1;
+    form_claim
+        .qualifiers
+        .push((Pid(407), ClaimValueData::Item(Qid(1860))));
+    form_claim.references.push(ReferenceGroup {
+        claims: vec![(consts::STATED_IN, ClaimValueData::Item(Qid(1_234)))],
+        hash: "abc".to_string(),
+    });
+
+    let stripped = referenced.stripped();
+    let form_claim = &stripped.lexeme.as_ref().unwrap().forms[0].claims[0].1;
+    assert!(form_claim.qualifiers.is_empty());
+    assert!(form_claim.references.is_empty());
+    assert_eq!(stripped, e);
+    let mut in_place = referenced.clone();
+    in_place.strip_references();
+    assert_eq!(in_place, stripped);
+
+    // the claims of forms and senses are compared like the entity's own claims
+    let mut other = referenced.clone();
+    let form = &mut other.lexeme.as_mut().unwrap().forms[0];
+    form.claims[0].1.id = "L7-F1$something-else".to_string();
+    form.claims[0].1.references[0].hash = "def".to_string();
+    assert!(referenced.data_eq(&other));
+    assert!(!referenced.data_eq(&e));
+    other.lexeme.as_mut().unwrap().senses[0].claims.clear();
+    assert!(!referenced.data_eq(&other));
+}
+
+#[test]
+fn commons_mediainfo() {
+    let j: serde_json::Value =
+        serde_json::from_str(include_str!("../items/M74698470.json")).unwrap();
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.id, WikiId::MediaInfoId(Mid(74_698_470)));
+    assert_eq!(e.entity_type, EntityType::MediaInfo);
+    assert_eq!(e.labels[&Lang("en".to_string())], "Douglas Adams");
+    assert_eq!(
+        e.claims,
+        vec![(
+            Pid(180),
+            ClaimValue {
+                data: ClaimValueData::Item(Qid(42)),
+                rank: Rank::Normal,
+                id: "M74698470$8b7e3c6d-4a3f-8e2b-1c9d-0f5a6b7c8d9e".to_string(),
+                qualifiers: Vec::new(),
+                references: Vec::new(),
+            }
+        )]
+    );
+    let json = e.to_wikibase_json();
+    assert!(json.get("claims").is_none());
+    assert_eq!(Entity::from_json(json).unwrap(), e);
+
+    let j = serde_json::json!({ "type": "mediainfo", "id": 7, "statements": {} });
+    assert_eq!(
+        Entity::from_json(j).unwrap().id,
+        WikiId::MediaInfoId(Mid(7))
+    );
+}
+
+#[test]
+fn snaktypes() {
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "claims": {
+            "P40": [
+                { "mainsnak": { "snaktype": "novalue", "property": "P40" }, "rank": "normal", "id": "Q7$1" },
+                {
+                    "mainsnak": { "snaktype": "somevalue", "property": "P40" },
+                    "rank": "normal",
+                    "id": "Q7$2",
+                    "qualifiers": {
+                        "P580": [{ "snaktype": "somevalue", "property": "P580" }]
+                    },
+                    "qualifiers-order": ["P580"]
+                }
+            ]
+        }
+    });
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.claims[0].1.snaktype(), Snaktype::NoValue);
+    assert_eq!(e.claims[1].1.snaktype(), Snaktype::SomeValue);
+    assert_eq!(
+        e.claims[1].1.qualifiers[0].1.snaktype(),
+        Snaktype::SomeValue
+    );
+    assert_eq!(
+        e.pid_claims(Pid(40))
+            .map(|claim| claim.data.snaktype())
+            .collect::<Vec<_>>(),
+        vec![Snaktype::NoValue, Snaktype::SomeValue]
+    );
+}
+
+#[test]
+fn label_fallback() {
+    let j = serde_json::json!({
+        "type": "item",
+        "id": "Q7",
+        "labels": {
+            "de": { "language": "de", "value": "Foo (de)" },
+            "mul": { "language": "mul", "value": "Foo" }
+        },
+        "descriptions": { "de": { "language": "de", "value": "ein Foo" } },
+        "claims": {}
+    });
+    let e = Entity::from_json(j).unwrap();
+    assert_eq!(e.label("de"), Some("Foo (de)"));
+    assert_eq!(e.label("en"), None);
+    assert_eq!(e.description("de"), Some("ein Foo"));
+    assert_eq!(e.description("en"), None);
+    assert_eq!(e.label_with_fallback(&["fr", "de", "en"]), Some("Foo (de)"));
+    assert_eq!(e.label_with_fallback(&["fr", "en"]), Some("Foo"));
+    assert_eq!(e.label_with_fallback(&[]), Some("Foo"));
+
+    // blank labels set in code are skipped
+    let e = EntityBuilder::new(WikiId::EntityId(Qid(7)))
+        .label(Lang::fr(), "")
+        .label(Lang::de(), "  ")
+        .label(Lang::en(), "Foo")
+        .build();
+    assert_eq!(e.label_with_fallback(&["fr", "de", "en"]), Some("Foo"));
+    assert_eq!(e.label_with_fallback(&["fr", "de"]), None);
+}
+
+#[test]
+fn multiple_entities() {
+    let q42: serde_json::Value = serde_json::from_str(include_str!("../items/Q42.json")).unwrap();
+    let q513: serde_json::Value = serde_json::from_str(include_str!("../items/Q513.json")).unwrap();
+    let j = serde_json::json!({
+        "entities": {
+            "Q42": q42["entities"]["Q42"],
+            "Q513": q513["entities"]["Q513"],
+            "Q999999999": { "id": "Q999999999", "missing": "" },
+            // redirects are listed under the ID that was asked for
+            "Q100": q513["entities"]["Q513"],
+        }
+    });
+    assert_eq!(
+        Entity::from_json(j.clone()),
+        Err(EntityError::MultipleEntities)
+    );
+    let entities = Entity::from_json_multi(j).unwrap();
+    let ids: Vec<_> = entities
+        .iter()
+        .map(|(key, entity)| (*key, entity.id))
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            (WikiId::EntityId(Qid(100)), WikiId::EntityId(Qid(513))),
+            (WikiId::EntityId(Qid(42)), WikiId::EntityId(Qid(42))),
+            (WikiId::EntityId(Qid(513)), WikiId::EntityId(Qid(513))),
+        ]
+    );
+
+    assert_eq!(
+        Entity::from_json_multi(q42["entities"]["Q42"].clone()),
+        Err(EntityError::NoEntities)
+    );
+}
+
+#[test]
+fn redirect() {
+    let j = serde_json::json!({ "entity": "Q123", "redirect": "Q456" });
+    let err = Entity::from_json(j).unwrap_err();
+    assert_eq!(
+        err,
+        EntityError::Redirect {
+            from: WikiId::EntityId(Qid(123)),
+            to: WikiId::EntityId(Qid(456)),
+        }
+    );
+    assert_eq!(err.to_string(), "Q123 is a redirect to Q456");
+
+    let j = serde_json::json!({ "entities": { "L1": { "entity": "L1", "redirect": "L2" } } });
+    assert_eq!(
+        Entity::from_json(j),
+        Err(EntityError::Redirect {
+            from: WikiId::LexemeId(Lid(1)),
+            to: WikiId::LexemeId(Lid(2)),
+        })
+    );
+
+    let j = serde_json::json!({ "redirect": "Q456" });
+    assert_eq!(Entity::from_json(j), Err(EntityError::NoId));
+
+    let j = serde_json::json!({ "entity": "Q123", "redirect": "nonsense" });
+    assert_eq!(Entity::from_json(j), Err(EntityError::BadId));
+}
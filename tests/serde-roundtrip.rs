@@ -0,0 +1,219 @@
+// test that entities survive a round trip through the crate's own serialization format
+
+use chrono::{TimeZone, Utc};
+use std::collections::BTreeMap;
+use wikidata::*;
+
+fn claim(data: ClaimValueData) -> ClaimValue {
+    ClaimValue {
+        data,
+        rank: Rank::Normal,
+        id: "Q1$00000000-0000-0000-0000-000000000000".to_string(),
+        qualifiers: Vec::new(),
+        references: Vec::new(),
+    }
+}
+
+fn text(text: &str, lang: &str) -> Text {
+    Text {
+        text: text.to_string(),
+        lang: Lang(lang.to_string()),
+    }
+}
+
+fn roundtrip(entity: &Entity) {
+    let json = serde_json::to_string(entity).unwrap();
+    let parsed: Entity = serde_json::from_str(&json).unwrap();
+    assert_eq!(&parsed, entity);
+}
+
+fn every_variant() -> Vec<ClaimValueData> {
+    vec![
+        ClaimValueData::CommonsMedia("Example.jpg".to_string()),
+        ClaimValueData::GlobeCoordinate {
+            lat: -33.856_944,
+            lon: 151.215_278,
+            precision: Some(0.000_277_777_777_777_78),
+            globe: consts::EARTH.into(),
+        },
+        ClaimValueData::Item(Qid(42)),
+        ClaimValueData::Property(Pid(31)),
+        ClaimValueData::String("a string".to_string()),
+        ClaimValueData::MonolingualText(text("Douglas Adams", "en")),
+        ClaimValueData::MultilingualText(vec![text("Erde", "de"), text("Earth", "en")]),
+        ClaimValueData::ExternalID("0000 0000 7835 8186".to_string()),
+        ClaimValueData::Quantity {
+            amount: 1.96,
+            lower_bound: Some(1.955),
+            upper_bound: Some(1.965),
+            unit: Some(consts::METRE.into()),
+        },
+        ClaimValueData::GlobeCoordinate {
+            lat: 0.0,
+            lon: 0.0,
+            precision: None,
+            globe: EntityOrIri::Iri("http://example.org/moon".to_string()),
+        },
+        ClaimValueData::Quantity {
+            amount: -1e300,
+            lower_bound: None,
+            upper_bound: None,
+            unit: None,
+        },
+        ClaimValueData::DateTime {
+            date_time: Utc.with_ymd_and_hms(1952, 3, 11, 0, 0, 0).unwrap(),
+            precision: 11,
+            calendar: consts::PROLEPTIC_GREGORIAN_CALENDAR,
+        },
+        ClaimValueData::DateTime {
+            date_time: Utc.with_ymd_and_hms(-200_000, 1, 1, 0, 0, 0).unwrap(),
+            precision: 4,
+            calendar: consts::PROLEPTIC_GREGORIAN_CALENDAR,
+        },
+        ClaimValueData::DateTime {
+            date_time: Utc.with_ymd_and_hms(200_000, 1, 1, 0, 0, 0).unwrap(),
+            precision: 6,
+            calendar: consts::PROLEPTIC_JULIAN_CALENDAR,
+        },
+        ClaimValueData::DateTime {
+            date_time: Utc.with_ymd_and_hms(2001, 1, 15, 12, 34, 56).unwrap(),
+            precision: 14,
+            calendar: consts::PROLEPTIC_GREGORIAN_CALENDAR,
+        },
+        ClaimValueData::OutOfRangeTime {
+            time: WbTime {
+                year: -4_540_000_000,
+                month: None,
+                day: None,
+                hour: None,
+                minute: None,
+                second: None,
+                precision: 3,
+            },
+            calendar: consts::PROLEPTIC_GREGORIAN_CALENDAR,
+        },
+        ClaimValueData::Url("https://example.org/".to_string()),
+        ClaimValueData::MathExpr("E = mc^2".to_string()),
+        ClaimValueData::GeoShape("Data:Sydney.map".to_string()),
+        ClaimValueData::MusicNotation("\\relative c' { c d e }".to_string()),
+        ClaimValueData::TabularData("Data:Population.tab".to_string()),
+        ClaimValueData::Lexeme(Lid(7)),
+        ClaimValueData::Form(Fid(Lid(7), 2)),
+        ClaimValueData::Sense(Sid(Lid(7), 1)),
+        ClaimValueData::NoValue,
+        ClaimValueData::UnknownValue,
+    ]
+}
+
+#[test]
+fn every_claim_variant() {
+    let claims = every_variant()
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| (Pid(i as u64 + 1), claim(data)))
+        .collect();
+    roundtrip(&Entity {
+        id: WikiId::EntityId(Qid(1)),
+        claims,
+        entity_type: EntityType::Entity,
+        descriptions: BTreeMap::new(),
+        labels: BTreeMap::new(),
+        aliases: BTreeMap::new(),
+        sitelinks: BTreeMap::new(),
+        lexeme: None,
+    });
+}
+
+#[test]
+fn qualifiers_references_and_terms() {
+    let mut value = claim(ClaimValueData::Item(Qid(5)));
+    value.rank = Rank::Preferred;
+    value.qualifiers = every_variant()
+        .into_iter()
+        .map(|data| (consts::DATE_OF_BIRTH, data))
+        .collect();
+    value.references = vec![ReferenceGroup {
+        claims: every_variant()
+            .into_iter()
+            .map(|data| (consts::STATED_IN, data))
+            .collect(),
+        hash: "fa278ebfc458360e5aed63d5058cca83c46134f1".to_string(),
+    }];
+    let mut deprecated = claim(ClaimValueData::Item(Qid(6)));
+    deprecated.rank = Rank::Deprecated;
+
+    let mut labels = BTreeMap::new();
+    labels.insert(Lang("en".to_string()), "Douglas Adams".to_string());
+    labels.insert(Lang("zh-hans".to_string()), "道格拉斯·亚当斯".to_string());
+    let mut descriptions = BTreeMap::new();
+    descriptions.insert(Lang("en".to_string()), "English writer".to_string());
+    let mut aliases = BTreeMap::new();
+    aliases.insert(
+        Lang("en".to_string()),
+        vec!["Douglas Noel Adams".to_string(), "DNA".to_string()],
+    );
+    let mut sitelinks = BTreeMap::new();
+    sitelinks.insert(
+        SiteName("enwiki".to_string()),
+        SitelinkValue {
+            title: "Douglas Adams".to_string(),
+            badges: vec![Qid(17_437_796)],
+            url: Some("https://en.wikipedia.org/wiki/Douglas_Adams".to_string()),
+        },
+    );
+    sitelinks.insert(
+        SiteName("dewiki".to_string()),
+        SitelinkValue {
+            title: "Douglas Adams".to_string(),
+            badges: Vec::new(),
+            url: None,
+        },
+    );
+
+    roundtrip(&Entity {
+        id: WikiId::EntityId(Qid(42)),
+        claims: vec![
+            (consts::INSTANCE_OF, value),
+            (consts::INSTANCE_OF, deprecated),
+        ],
+        entity_type: EntityType::Entity,
+        descriptions,
+        labels,
+        aliases,
+        sitelinks,
+        lexeme: None,
+    });
+}
+
+#[test]
+fn property_and_lexeme_ids() {
+    for (id, entity_type) in [
+        (WikiId::PropertyId(Pid(31)), EntityType::Property),
+        (WikiId::LexemeId(Lid(7)), EntityType::Lexeme),
+    ] {
+        roundtrip(&Entity {
+            id,
+            claims: Vec::new(),
+            entity_type,
+            descriptions: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            sitelinks: BTreeMap::new(),
+            lexeme: None,
+        });
+    }
+}
+
+#[test]
+fn wikidata_items() {
+    for json in [
+        include_str!("../items/Q1.json"),
+        include_str!("../items/Q42.json"),
+        include_str!("../items/Q513.json"),
+        include_str!("../items/Q31928.json"),
+        include_str!("../items/lexeme-cat.json"),
+    ] {
+        let j: serde_json::Value = serde_json::from_str(json).unwrap();
+        roundtrip(&Entity::from_json(j).unwrap());
+    }
+}